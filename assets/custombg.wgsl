@@ -1,12 +1,8 @@
-#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
-#import bevy_sprite::mesh2d_view_bindings::view
-#import bevy_sprite::mesh2d_bindings
-#import bevy_sprite::mesh2d_functions::mesh2d_position_world_to_clip
-
-#import braymatter::bglib::scroll
+#import braymatter::bglib::{BackgroundVertexOutput, scroll_layer, SCALING_SCREEN}
 
 struct Uniforms {
-    scale: f32,
+    scale: vec2<f32>,
+    scroll: vec2<f32>,
     blend_color: vec4<f32>
 };
 
@@ -19,11 +15,21 @@ var texture_sampler: sampler;
 
 @fragment
 fn fragment(
-    in: FullscreenVertexOutput,
+    in: BackgroundVertexOutput,
 ) -> @location(0) vec4<f32> {
-    let scale = uniforms.scale;
-    let offset = mesh2d_position_world_to_clip(vec4<f32>(view.world_position.xy, 0.0, 0.0)).xy;
-    let color = scroll(texture, texture_sampler, scale, in.uv, offset, view.viewport.zw) + uniforms.blend_color;
-    return color;
+    let color = scroll_layer(
+        texture,
+        texture_sampler,
+        in.uv,
+        uniforms.scale,
+        vec2<f32>(0.0),
+        vec2<f32>(0.0),
+        uniforms.scroll,
+        vec2<f32>(1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0),
+        SCALING_SCREEN,
+    );
+    return color + uniforms.blend_color;
 }
-
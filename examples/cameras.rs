@@ -0,0 +1,125 @@
+use bevy::{color::palettes::css, prelude::*, render::camera::Viewport, window::WindowResized};
+use bevy_tiling_background::{
+    BackgroundCamera, BackgroundMaterial, TilingBackground, TilingBackgroundPlugin,
+};
+
+pub fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TilingBackgroundPlugin::<BackgroundMaterial>::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (movement, place_minimap))
+        .run();
+}
+
+pub fn setup(
+    mut commands: Commands,
+    asset_server: ResMut<AssetServer>,
+    mut materials: ResMut<Assets<BackgroundMaterial>>,
+) {
+    let image = asset_server.load("space_test.png");
+
+    let minimap_image = asset_server.load("test.png");
+
+    // Spawn cameras, the minimap is drawn on top of the gameplay camera.
+    let gameplay_camera = commands.spawn((Camera2d, IsDefaultUiCamera)).id();
+    let minimap_camera = commands
+        .spawn((
+            Camera2d,
+            Camera {
+                order: 1,
+                ..default()
+            },
+            OrthographicProjection {
+                scale: 4.0,
+                ..OrthographicProjection::default_2d()
+            },
+            Minimap,
+        ))
+        .id();
+
+    // Each background is only drawn by the camera it is bound to.
+    commands.spawn((
+        TilingBackground,
+        MeshMaterial2d(materials.add(BackgroundMaterial {
+            texture: image,
+            ..default()
+        })),
+        BackgroundCamera(gameplay_camera),
+    ));
+    commands.spawn((
+        TilingBackground,
+        MeshMaterial2d(materials.add(BackgroundMaterial {
+            texture: minimap_image,
+            ..default()
+        })),
+        BackgroundCamera(minimap_camera),
+    ));
+
+    // Instructions
+    commands.spawn((
+        TextFont::from_font_size(32.0),
+        Text::new("Arrow keys to move"),
+    ));
+
+    commands.spawn((
+        Sprite {
+            color: css::RED.into(),
+            ..default()
+        },
+        Transform::from_scale(Vec3::new(100.0, 100.0, 1.0))
+            .with_translation(Vec3::new(0.0, 50.0, 1.0)),
+        Name::new("Red Box"),
+    ));
+}
+
+#[derive(Component)]
+struct Minimap;
+
+fn movement(
+    mut cameras: Query<&mut Transform, With<Camera>>,
+    input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+) {
+    let move_speed = 100.0;
+    let mut direction = Vec2::ZERO;
+    if input.pressed(KeyCode::ArrowLeft) {
+        direction.x -= 1.0;
+    }
+
+    if input.pressed(KeyCode::ArrowRight) {
+        direction.x += 1.0;
+    }
+
+    if input.pressed(KeyCode::ArrowDown) {
+        direction.y -= 1.0;
+    }
+
+    if input.pressed(KeyCode::ArrowUp) {
+        direction.y += 1.0;
+    }
+
+    // Both cameras follow the same point, the minimap just sees more of the world.
+    for mut camera_transform in cameras.iter_mut() {
+        camera_transform.translation += (direction * time.delta_secs() * move_speed).extend(0.0);
+    }
+}
+
+fn place_minimap(
+    windows: Query<&Window>,
+    mut resize_events: EventReader<WindowResized>,
+    mut minimap: Query<&mut Camera, With<Minimap>>,
+) {
+    for resize_event in resize_events.read() {
+        let Ok(window) = windows.get(resize_event.window) else {
+            continue;
+        };
+        let size = window.physical_size() / 4;
+        let mut camera = minimap.single_mut();
+        camera.viewport = Some(Viewport {
+            physical_position: UVec2::new(window.physical_width() - size.x, 0),
+            physical_size: size,
+            ..default()
+        });
+    }
+}
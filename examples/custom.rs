@@ -1,9 +1,3 @@
-use bevy::core_pipeline::fullscreen_vertex_shader::FULLSCREEN_SHADER_HANDLE;
-use bevy::render::mesh::MeshVertexBufferLayoutRef;
-use bevy::render::render_resource::{
-    PrimitiveState, RenderPipelineDescriptor, SpecializedMeshPipelineError,
-};
-use bevy::sprite::Material2dKey;
 use bevy::{
     color::palettes::css,
     prelude::*,
@@ -12,8 +6,8 @@ use bevy::{
     sprite::Material2d,
 };
 use bevy_tiling_background::{
-    BackgroundMaterial, BackgroundMovementScale, CustomBackgroundImageBundle, ScrollingBackground,
-    SetImageRepeatingExt, TilingBackgroundPlugin,
+    BackgroundMaterial, BackgroundMovementScale, BackgroundScrollVelocity, ScrollingBackground,
+    TilingBackground, TilingBackgroundPlugin, TILED_BG_SHADER_HANDLE,
 };
 
 pub fn main() {
@@ -33,12 +27,11 @@ pub fn setup(
     mut materials: ResMut<Assets<CustomMaterial>>,
 ) {
     let image = asset_server.load("space_test.png");
-    // Queue a command to set the image to be repeating once the image is loaded.
-    commands.set_image_repeating(image.clone());
 
     // Set up a material
     let custom_mat = CustomMaterial {
-        movement_scale: -0.15,
+        movement_scale: Vec2::splat(-0.15),
+        scroll: Vec2::ZERO,
         texture: image,
         blend_color: css::CRIMSON.into(),
     };
@@ -47,12 +40,13 @@ pub fn setup(
     commands.spawn(Camera2d);
 
     // Spawn Background
-    commands
-        .spawn(CustomBackgroundImageBundle::with_material(
-            custom_mat,
-            materials.as_mut(),
-        ))
-        .insert(BackgroundMovementScale { scale: 0.00 });
+    commands.spawn((
+        TilingBackground,
+        MeshMaterial2d(materials.add(custom_mat)),
+        BackgroundMovementScale { scale: 0.00 },
+        // Drift slowly to the left even when the camera stands still.
+        BackgroundScrollVelocity(Vec2::new(-20.0, 0.0)),
+    ));
 
     // Instructions
     commands.spawn((
@@ -112,9 +106,10 @@ fn movement(
 #[derive(AsBindGroup, Debug, Clone, Asset, TypePath, Default)]
 pub struct CustomMaterial {
     #[uniform(0)]
-    pub movement_scale: f32,
-    /// This image must have its [`SamplerDescriptor`] address_mode_* fields set to
-    /// [`AddressMode::Repeat`].
+    pub movement_scale: Vec2,
+    #[uniform(0)]
+    pub scroll: Vec2,
+    /// Returned by `textures`, so the plugin sets its sampler to repeat.
     #[texture(1)]
     #[sampler(2)]
     pub texture: Handle<Image>,
@@ -124,26 +119,29 @@ pub struct CustomMaterial {
 }
 
 impl Material2d for CustomMaterial {
+    // Draws the background's fullscreen triangle, see `BackgroundVertexOutput`.
     fn vertex_shader() -> ShaderRef {
-        FULLSCREEN_SHADER_HANDLE.into()
+        TILED_BG_SHADER_HANDLE.into()
     }
     fn fragment_shader() -> ShaderRef {
         "custombg.wgsl".into()
     }
-
-    fn specialize(
-        descriptor: &mut RenderPipelineDescriptor,
-        _: &MeshVertexBufferLayoutRef,
-        _: Material2dKey<Self>,
-    ) -> Result<(), SpecializedMeshPipelineError> {
-        descriptor.primitive = PrimitiveState::default();
-        descriptor.vertex.entry_point = "fullscreen_vertex_shader".into();
-        Ok(())
-    }
 }
 
 impl ScrollingBackground for CustomMaterial {
     fn set_movement(&mut self, movement: f32) {
+        self.movement_scale = Vec2::splat(movement);
+    }
+
+    fn set_movement_axes(&mut self, movement: Vec2) {
         self.movement_scale = movement;
     }
+
+    fn set_scroll(&mut self, scroll: Vec2) {
+        self.scroll = scroll;
+    }
+
+    fn textures(&self) -> Vec<Handle<Image>> {
+        vec![self.texture.clone()]
+    }
 }
@@ -0,0 +1,153 @@
+use bevy::color::palettes::css;
+use bevy::prelude::*;
+use bevy_tiling_background::{
+    BackgroundBlendMode, BackgroundFlipbook, BackgroundMaterial, BackgroundMovementScale,
+    BackgroundStrip, LoadRepeatingExt, TilingBackground, TilingBackgroundPlugin,
+};
+
+const BLEND_MODES: [BackgroundBlendMode; 5] = [
+    BackgroundBlendMode::Blend,
+    BackgroundBlendMode::Premultiplied,
+    BackgroundBlendMode::Add,
+    BackgroundBlendMode::Multiply,
+    BackgroundBlendMode::Screen,
+];
+
+pub fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TilingBackgroundPlugin::<BackgroundMaterial>::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (movement, switch_blend_mode, update_instructions))
+        .run();
+}
+
+pub fn setup(
+    mut commands: Commands,
+    asset_server: ResMut<AssetServer>,
+    mut materials: ResMut<Assets<BackgroundMaterial>>,
+) {
+    commands.spawn(Camera2d);
+
+    // The bottom layer covers everything, so it doesn't need blending. It plays the four quarters
+    // of the image as a flipbook, two frames per second.
+    commands.spawn((
+        TilingBackground,
+        MeshMaterial2d(materials.add(BackgroundMaterial {
+            texture: asset_server.load_repeating("space_test.png"),
+            blend_mode: BackgroundBlendMode::Opaque,
+            ..default()
+        })),
+        Transform::from_xyz(0.0, 0.0, 0.1),
+        BackgroundFlipbook::from_grid(2, 2, 2.0),
+    ));
+
+    // A single row of tiles with its bottom edge at y = -150, dimming everything below it.
+    commands.spawn((
+        TilingBackground,
+        MeshMaterial2d(materials.add(BackgroundMaterial {
+            texture: asset_server.load_repeating("test.png"),
+            opacity: 0.5,
+            ..default()
+        })),
+        Transform::from_xyz(0.0, 0.0, 0.2),
+        BackgroundMovementScale { scale: 0.5 },
+        BackgroundStrip::horizontal(-150.0).with_fill(css::BLACK.with_alpha(0.5), Color::NONE),
+    ));
+
+    // The dust in front cycles through the other blend modes.
+    commands.spawn((
+        TilingBackground,
+        MeshMaterial2d(materials.add(BackgroundMaterial {
+            texture: asset_server.load_repeating("space_dust_transparent.png"),
+            blend_mode: BLEND_MODES[0],
+            ..default()
+        })),
+        Transform::from_xyz(0.0, 0.0, 2.1),
+        BackgroundMovementScale { scale: 1.1 },
+        DustLayer,
+    ));
+
+    // Instructions
+    commands.spawn((
+        TextFont::from_font_size(32.0),
+        Text::new("Arrow keys to move\n"),
+        Instructions,
+    ));
+
+    commands
+        .spawn((
+            Sprite::from_image(asset_server.load("ship.png")),
+            Transform::from_xyz(0.0, 50.0, 1.0),
+        ))
+        .insert(Player);
+}
+
+#[derive(Component)]
+struct Instructions;
+
+#[derive(Component)]
+struct Player;
+
+#[derive(Component)]
+struct DustLayer;
+
+fn movement(
+    mut camera: Query<&mut Transform, With<Camera>>,
+    mut sprite_transform: Query<&mut Transform, (With<Player>, Without<Camera>)>,
+    input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+) {
+    let move_speed = 100.0;
+    let mut direction = Vec3::ZERO;
+    if input.pressed(KeyCode::ArrowLeft) {
+        direction.x -= 1.0;
+    }
+    if input.pressed(KeyCode::ArrowRight) {
+        direction.x += 1.0;
+    }
+    if input.pressed(KeyCode::ArrowDown) {
+        direction.y -= 1.0;
+    }
+    if input.pressed(KeyCode::ArrowUp) {
+        direction.y += 1.0;
+    }
+
+    let delta = direction * move_speed * time.delta_secs();
+    camera.single_mut().translation += delta;
+    sprite_transform.single_mut().translation += delta;
+}
+
+fn switch_blend_mode(
+    input: Res<ButtonInput<KeyCode>>,
+    dust: Query<&MeshMaterial2d<BackgroundMaterial>, With<DustLayer>>,
+    mut materials: ResMut<Assets<BackgroundMaterial>>,
+) {
+    if !input.just_pressed(KeyCode::Tab) {
+        return;
+    }
+    let Some(material) = materials.get_mut(&**dust.single()) else {
+        return;
+    };
+    let current = BLEND_MODES
+        .iter()
+        .position(|mode| *mode == material.blend_mode)
+        .unwrap_or_default();
+    material.blend_mode = BLEND_MODES[(current + 1) % BLEND_MODES.len()];
+}
+
+fn update_instructions(
+    mut instructions: Query<&mut Text, With<Instructions>>,
+    dust: Query<&MeshMaterial2d<BackgroundMaterial>, With<DustLayer>>,
+    materials: Res<Assets<BackgroundMaterial>>,
+) {
+    let Some(material) = materials.get(&**dust.single()) else {
+        return;
+    };
+    **instructions.single_mut() = format!(
+        "Arrow keys to move\n\
+        Tab to change the dust's blend mode\n\
+        Current blend mode {:?}",
+        material.blend_mode
+    );
+}
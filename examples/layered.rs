@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+use bevy_tiling_background::{
+    BackgroundLayer, BackgroundPass, LayeredBackgroundMaterial, LoadRepeatingExt, TilingBackground,
+    TilingBackgroundPlugin,
+};
+
+pub fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TilingBackgroundPlugin::<LayeredBackgroundMaterial>::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, movement)
+        .run();
+}
+
+pub fn setup(
+    mut commands: Commands,
+    asset_server: ResMut<AssetServer>,
+    mut materials: ResMut<Assets<LayeredBackgroundMaterial>>,
+) {
+    // Draws the background before any sprites, regardless of their z.
+    commands.spawn((Camera2d, BackgroundPass));
+
+    // Both layers are drawn in a single pass, the first one is the furthest back.
+    commands.spawn((
+        TilingBackground,
+        MeshMaterial2d(
+            materials.add(LayeredBackgroundMaterial::new([
+                BackgroundLayer::new(asset_server.load_repeating("space_test.png")),
+                BackgroundLayer::new(asset_server.load_repeating("space_dust_transparent.png"))
+                    .with_movement_scale(1.1),
+            ])),
+        ),
+        Transform::from_xyz(0.0, 0.0, 0.1),
+    ));
+
+    // Instructions
+    commands.spawn((
+        TextFont::from_font_size(32.0),
+        Text::new("Arrow keys to move\n+/- to change the parallax of the front layer"),
+    ));
+}
+
+fn movement(
+    mut camera: Query<&mut Transform, With<Camera>>,
+    backgrounds: Query<&MeshMaterial2d<LayeredBackgroundMaterial>>,
+    mut materials: ResMut<Assets<LayeredBackgroundMaterial>>,
+    input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+) {
+    let move_speed = 100.0;
+    let mut camera_transform = camera.single_mut();
+    if input.pressed(KeyCode::ArrowLeft) {
+        camera_transform.translation.x -= time.delta_secs() * move_speed;
+    }
+
+    if input.pressed(KeyCode::ArrowRight) {
+        camera_transform.translation.x += time.delta_secs() * move_speed;
+    }
+
+    if input.pressed(KeyCode::ArrowDown) {
+        camera_transform.translation.y -= time.delta_secs() * move_speed;
+    }
+
+    if input.pressed(KeyCode::ArrowUp) {
+        camera_transform.translation.y += time.delta_secs() * move_speed;
+    }
+
+    let mut change = 0.0;
+    if input.pressed(KeyCode::Equal) || input.pressed(KeyCode::NumpadAdd) {
+        change += time.delta_secs();
+    }
+    if input.pressed(KeyCode::Minus) || input.pressed(KeyCode::NumpadSubtract) {
+        change -= time.delta_secs();
+    }
+    if change == 0.0 {
+        return;
+    }
+    for material in backgrounds.iter() {
+        if let Some(front_layer) = materials
+            .get_mut(&material.0)
+            .and_then(|material| material.layers.last_mut())
+        {
+            front_layer.movement_scale += change;
+        }
+    }
+}
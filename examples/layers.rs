@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 use bevy_tiling_background::{
-    BackgroundImageBundle, BackgroundMaterial, BackgroundMovementScale, SetImageRepeatingExt,
+    BackgroundMaterial, BackgroundMovementScale, LoadRepeatingExt, TilingBackground,
     TilingBackgroundPlugin,
 };
 
@@ -18,24 +18,31 @@ pub fn setup(
     asset_server: ResMut<AssetServer>,
     mut materials: ResMut<Assets<BackgroundMaterial>>,
 ) {
-    let image = asset_server.load("space_test.png");
-    // Queue a command to set the image to be repeating once the image is loaded.
-    commands.set_image_repeating(image.clone());
-
-    let front_layer = asset_server.load("space_dust_transparent.png");
-    // Queue a command to set the image to be repeating once the image is loaded.
-    commands.set_image_repeating(front_layer.clone());
+    // Load the images with a repeating sampler, so they tile correctly from the first frame on.
+    let image = asset_server.load_repeating("space_test.png");
+    let front_layer = asset_server.load_repeating("space_dust_transparent.png");
 
     // Spawn camera
     commands.spawn(Camera2d);
 
     // Spawn backgrounds
-    commands.spawn(BackgroundImageBundle::from_image(image, materials.as_mut()).at_z_layer(0.1));
-    commands.spawn(
-        BackgroundImageBundle::from_image(front_layer, materials.as_mut())
-            .at_z_layer(2.1)
-            .with_movement_scale(1.1),
-    );
+    commands.spawn((
+        TilingBackground,
+        MeshMaterial2d(materials.add(BackgroundMaterial {
+            texture: image,
+            ..default()
+        })),
+        Transform::from_xyz(0.0, 0.0, 0.1),
+    ));
+    commands.spawn((
+        TilingBackground,
+        MeshMaterial2d(materials.add(BackgroundMaterial {
+            texture: front_layer,
+            ..default()
+        })),
+        Transform::from_xyz(0.0, 0.0, 2.1),
+        BackgroundMovementScale { scale: 1.1 },
+    ));
 
     // Instructions
     commands.spawn((
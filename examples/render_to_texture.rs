@@ -0,0 +1,129 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+        view::RenderLayers,
+    },
+};
+use bevy_tiling_background::{
+    BackgroundCamera, BackgroundMaterial, BackgroundMovementScale, TilingBackground,
+    TilingBackgroundPlugin,
+};
+
+pub fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TilingBackgroundPlugin::<BackgroundMaterial>::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, movement)
+        .run();
+}
+
+/// Everything drawn on the in-game monitor lives on this layer.
+const MONITOR_LAYER: usize = 1;
+
+pub fn setup(
+    mut commands: Commands,
+    asset_server: ResMut<AssetServer>,
+    mut materials: ResMut<Assets<BackgroundMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let image = asset_server.load("space_test.png");
+
+    let front_layer = asset_server.load("space_dust_transparent.png");
+
+    // The texture the monitor camera renders into.
+    let mut monitor_image = Image::new_fill(
+        Extent3d {
+            width: 512,
+            height: 384,
+            ..default()
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Bgra8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    monitor_image.texture_descriptor.usage =
+        TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT;
+    let monitor_image = images.add(monitor_image);
+
+    // Spawn cameras, the monitor camera renders before the main camera so its texture is ready.
+    let monitor_camera = commands
+        .spawn((
+            Camera2d,
+            Camera {
+                order: -1,
+                target: monitor_image.clone().into(),
+                ..default()
+            },
+            RenderLayers::layer(MONITOR_LAYER),
+            MonitorCamera,
+        ))
+        .id();
+    commands.spawn(Camera2d);
+
+    // Only drawn by the monitor camera, filling the texture it renders to.
+    commands.spawn((
+        TilingBackground,
+        MeshMaterial2d(materials.add(BackgroundMaterial {
+            texture: image,
+            ..default()
+        })),
+        Transform::from_xyz(0.0, 0.0, 0.1),
+        BackgroundCamera(monitor_camera),
+    ));
+    commands.spawn((
+        TilingBackground,
+        MeshMaterial2d(materials.add(BackgroundMaterial {
+            texture: front_layer,
+            ..default()
+        })),
+        Transform::from_xyz(0.0, 0.0, 2.1),
+        BackgroundMovementScale { scale: 1.1 },
+        BackgroundCamera(monitor_camera),
+    ));
+
+    commands.spawn((
+        Sprite::from_image(asset_server.load("ship.png")),
+        Transform::from_xyz(0.0, 0.0, 1.0),
+        RenderLayers::layer(MONITOR_LAYER),
+    ));
+
+    // The monitor itself, shown by the main camera.
+    commands.spawn(Sprite::from_image(monitor_image));
+
+    // Instructions
+    commands.spawn((
+        TextFont::from_font_size(32.0),
+        Text::new("Arrow keys to move the monitor camera"),
+    ));
+}
+
+#[derive(Component)]
+struct MonitorCamera;
+
+fn movement(
+    mut camera: Query<&mut Transform, With<MonitorCamera>>,
+    input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+) {
+    let move_speed = 100.0;
+    let mut camera_transform = camera.single_mut();
+    if input.pressed(KeyCode::ArrowLeft) {
+        camera_transform.translation.x -= time.delta_secs() * move_speed;
+    }
+
+    if input.pressed(KeyCode::ArrowRight) {
+        camera_transform.translation.x += time.delta_secs() * move_speed;
+    }
+
+    if input.pressed(KeyCode::ArrowDown) {
+        camera_transform.translation.y -= time.delta_secs() * move_speed;
+    }
+
+    if input.pressed(KeyCode::ArrowUp) {
+        camera_transform.translation.y += time.delta_secs() * move_speed;
+    }
+}
@@ -0,0 +1,129 @@
+use bevy::{prelude::*, render::camera::Viewport, window::WindowResized};
+use bevy_tiling_background::{
+    BackgroundMaterial, BackgroundMovementScale, TilingBackground, TilingBackgroundPlugin,
+};
+
+pub fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TilingBackgroundPlugin::<BackgroundMaterial>::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (movement, set_camera_viewports))
+        .run();
+}
+
+pub fn setup(
+    mut commands: Commands,
+    asset_server: ResMut<AssetServer>,
+    mut materials: ResMut<Assets<BackgroundMaterial>>,
+) {
+    let image = asset_server.load("space_test.png");
+
+    let front_layer = asset_server.load("space_dust_transparent.png");
+
+    // One camera per player, each gets half of the window in `set_camera_viewports`.
+    commands.spawn((Camera2d, Player::Left, IsDefaultUiCamera));
+    commands.spawn((
+        Camera2d,
+        Camera {
+            order: 1,
+            ..default()
+        },
+        Player::Right,
+    ));
+
+    // The backgrounds are shared, each camera scrolls them from its own position.
+    commands.spawn((
+        TilingBackground,
+        MeshMaterial2d(materials.add(BackgroundMaterial {
+            texture: image,
+            ..default()
+        })),
+        Transform::from_xyz(0.0, 0.0, 0.1),
+    ));
+    commands.spawn((
+        TilingBackground,
+        MeshMaterial2d(materials.add(BackgroundMaterial {
+            texture: front_layer,
+            ..default()
+        })),
+        Transform::from_xyz(0.0, 0.0, 2.1),
+        BackgroundMovementScale { scale: 1.1 },
+    ));
+
+    // Instructions
+    commands.spawn((
+        TextFont::from_font_size(32.0),
+        Text::new("WASD to move the left camera\nArrow keys to move the right camera"),
+    ));
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum Player {
+    Left,
+    Right,
+}
+
+impl Player {
+    fn keys(self) -> [KeyCode; 4] {
+        match self {
+            Player::Left => [KeyCode::KeyA, KeyCode::KeyD, KeyCode::KeyS, KeyCode::KeyW],
+            Player::Right => [
+                KeyCode::ArrowLeft,
+                KeyCode::ArrowRight,
+                KeyCode::ArrowDown,
+                KeyCode::ArrowUp,
+            ],
+        }
+    }
+}
+
+fn movement(
+    mut cameras: Query<(&mut Transform, &Player)>,
+    input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+) {
+    let move_speed = 100.0;
+    for (mut camera_transform, player) in cameras.iter_mut() {
+        let [left, right, down, up] = player.keys();
+        if input.pressed(left) {
+            camera_transform.translation.x -= time.delta_secs() * move_speed;
+        }
+
+        if input.pressed(right) {
+            camera_transform.translation.x += time.delta_secs() * move_speed;
+        }
+
+        if input.pressed(down) {
+            camera_transform.translation.y -= time.delta_secs() * move_speed;
+        }
+
+        if input.pressed(up) {
+            camera_transform.translation.y += time.delta_secs() * move_speed;
+        }
+    }
+}
+
+fn set_camera_viewports(
+    windows: Query<&Window>,
+    mut resize_events: EventReader<WindowResized>,
+    mut cameras: Query<(&mut Camera, &Player)>,
+) {
+    for resize_event in resize_events.read() {
+        let Ok(window) = windows.get(resize_event.window) else {
+            continue;
+        };
+        let size = UVec2::new(window.physical_width() / 2, window.physical_height());
+        for (mut camera, player) in cameras.iter_mut() {
+            let x = match player {
+                Player::Left => 0,
+                Player::Right => size.x,
+            };
+            camera.viewport = Some(Viewport {
+                physical_position: UVec2::new(x, 0),
+                physical_size: size,
+                ..default()
+            });
+        }
+    }
+}
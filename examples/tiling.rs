@@ -1,6 +1,6 @@
 use bevy::{color::palettes::css, prelude::*};
 use bevy_tiling_background::{
-    BackgroundImageBundle, BackgroundMaterial, BackgroundMovementScale, SetImageRepeatingExt,
+    BackgroundMaterial, BackgroundMovementScale, BackgroundScaling, TilingBackground,
     TilingBackgroundPlugin,
 };
 
@@ -10,8 +10,8 @@ pub fn main() {
         .add_plugins(TilingBackgroundPlugin::<BackgroundMaterial>::default())
         .add_systems(Startup, setup)
         .add_systems(Update, movement)
+        .add_systems(Update, zoom)
         .add_systems(Update, update_instructions)
-        .add_systems(PostUpdate, update_movement_scale_system)
         .run();
 }
 
@@ -21,12 +21,17 @@ pub fn setup(
     mut materials: ResMut<Assets<BackgroundMaterial>>,
 ) {
     let image = asset_server.load("test.png");
-    // Queue a command to set the image to be repeating once the image is loaded.
-    commands.set_image_repeating(image.clone());
 
     commands.spawn(Camera2d);
 
-    commands.spawn(BackgroundImageBundle::from_image(image, materials.as_mut()).at_z_layer(0.1));
+    commands.spawn((
+        TilingBackground,
+        MeshMaterial2d(materials.add(BackgroundMaterial {
+            texture: image,
+            ..default()
+        })),
+        Transform::from_xyz(0.0, 0.0, 0.1),
+    ));
 
     // Instructions
     commands.spawn((
@@ -98,32 +103,43 @@ fn movement(
     }
 }
 
+fn zoom(
+    mut projection: Query<&mut OrthographicProjection, With<Camera>>,
+    mut background_scaling: Query<&mut BackgroundScaling>,
+    input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+) {
+    let mut projection = projection.single_mut();
+    if input.pressed(KeyCode::PageUp) {
+        projection.scale *= 1.0 + time.delta_secs();
+    }
+
+    if input.pressed(KeyCode::PageDown) {
+        projection.scale /= 1.0 + time.delta_secs();
+    }
+
+    if input.just_pressed(KeyCode::Tab) {
+        for mut scaling in background_scaling.iter_mut() {
+            *scaling = match *scaling {
+                BackgroundScaling::Screen => BackgroundScaling::World,
+                BackgroundScaling::World => BackgroundScaling::Screen,
+            };
+        }
+    }
+}
+
 fn update_instructions(
     mut query: Query<&mut Text, With<Instructions>>,
-    background_movement: Query<&BackgroundMovementScale>,
+    background_movement: Query<(&BackgroundMovementScale, &BackgroundScaling)>,
 ) {
     let mut instructions = query.single_mut();
+    let (movement, scaling) = background_movement.single();
     **instructions = format!(
         "Arrow keys to move\n\
         +/- to change parallax \n\
-        Current parallax multiplier {}",
-        background_movement.single().scale
+        PageUp/PageDown to zoom, Tab to toggle scaling\n\
+        Current parallax multiplier {}\n\
+        Current scaling {:?}",
+        movement.scale, scaling
     );
 }
-
-pub fn update_movement_scale_system(
-    query: Query<
-        (
-            &MeshMaterial2d<BackgroundMaterial>,
-            &BackgroundMovementScale,
-        ),
-        Changed<BackgroundMovementScale>,
-    >,
-    mut background_materials: ResMut<Assets<BackgroundMaterial>>,
-) {
-    for (bg_material_handle, scale) in query.iter() {
-        if let Some(background_material) = background_materials.get_mut(&**bg_material_handle) {
-            background_material.movement_scale = scale.scale;
-        }
-    }
-}
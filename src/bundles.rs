@@ -0,0 +1,99 @@
+//! Deprecated bundles kept around so code written against 0.12 and earlier keeps compiling.
+#![allow(deprecated)]
+
+use bevy::prelude::*;
+use bevy::render::view::NoFrustumCulling;
+use bevy::sprite::Material2d;
+
+use crate::{
+    BackgroundMaterial, BackgroundMovementScale, ScrollingBackground, TilingBackground,
+    BG_MESH_HANDLE,
+};
+
+#[deprecated(
+    since = "0.13.0",
+    note = "Spawn `(TilingBackground, MeshMaterial2d(handle))` instead"
+)]
+#[derive(Bundle)]
+pub struct CustomBackgroundImageBundle<T: Material2d> {
+    pub material: MeshMaterial2d<T>,
+    pub mesh: Mesh2d,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    pub visibility: Visibility,
+    pub view_visibility: ViewVisibility,
+    pub inherited_visibility: InheritedVisibility,
+    pub movement_scale: BackgroundMovementScale,
+    pub no_frustum_culling: NoFrustumCulling,
+    pub tiling_background: TilingBackground,
+}
+
+impl<T: Material2d + ScrollingBackground> CustomBackgroundImageBundle<T> {
+    pub fn with_material(material: T, materials: &mut Assets<T>) -> Self {
+        Self {
+            material: materials.add(material).into(),
+            mesh: BG_MESH_HANDLE.into(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+            view_visibility: Default::default(),
+            inherited_visibility: Default::default(),
+            movement_scale: Default::default(),
+            no_frustum_culling: Default::default(),
+            tiling_background: TilingBackground,
+        }
+    }
+}
+
+#[deprecated(
+    since = "0.13.0",
+    note = "Spawn `(TilingBackground, MeshMaterial2d(handle))` instead"
+)]
+#[derive(Bundle)]
+pub struct BackgroundImageBundle {
+    pub material: MeshMaterial2d<BackgroundMaterial>,
+    pub mesh: Mesh2d,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    pub visibility: Visibility,
+    pub view_visibility: ViewVisibility,
+    pub inherited_visibility: InheritedVisibility,
+    pub movement_scale: BackgroundMovementScale,
+    pub no_frustum_culling: NoFrustumCulling,
+    pub tiling_background: TilingBackground,
+}
+
+impl BackgroundImageBundle {
+    pub fn from_image(
+        image: Handle<Image>,
+        background_materials: &mut Assets<BackgroundMaterial>,
+    ) -> Self {
+        Self {
+            material: background_materials
+                .add(BackgroundMaterial {
+                    texture: image,
+                    ..default()
+                })
+                .into(),
+            mesh: BG_MESH_HANDLE.into(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+            view_visibility: Default::default(),
+            inherited_visibility: Default::default(),
+            movement_scale: Default::default(),
+            no_frustum_culling: Default::default(),
+            tiling_background: TilingBackground,
+        }
+    }
+
+    pub fn with_movement_scale(mut self, scale: f32) -> Self {
+        self.movement_scale = BackgroundMovementScale::new(scale);
+        self
+    }
+
+    pub fn at_z_layer(mut self, z: f32) -> Self {
+        self.transform.translation.z = z;
+        self
+    }
+}
@@ -0,0 +1,292 @@
+//! [`LayeredBackgroundMaterial`], which draws several parallax layers in a single pass.
+
+use bevy::prelude::*;
+use bevy::render::mesh::MeshVertexBufferLayoutRef;
+use bevy::render::render_asset::RenderAssets;
+use bevy::render::render_resource::{
+    AsBindGroup, AsBindGroupShaderType, RenderPipelineDescriptor, ShaderRef,
+    SpecializedMeshPipelineError,
+};
+use bevy::render::texture::GpuImage;
+use bevy::sprite::{AlphaMode2d, Material2d, Material2dKey};
+
+use crate::{
+    BackgroundBlendMode, BackgroundMaterialKey, BackgroundMovementScale, BackgroundOffset,
+    BackgroundScaling, ScrollingBackground, LAYERED_BG_SHADER_HANDLE, TILED_BG_SHADER_HANDLE,
+};
+
+pub use uniforms::{BackgroundLayerUniform, LayeredBackgroundUniform};
+
+/// The most layers a [`LayeredBackgroundMaterial`] draws.
+pub const MAX_BACKGROUND_LAYERS: usize = 4;
+
+/// A single layer of a [`LayeredBackgroundMaterial`].
+#[derive(Reflect, Clone, Debug)]
+#[reflect(Default, Debug)]
+pub struct BackgroundLayer {
+    /// The tiled image. Its sampler is set to repeat once the background is spawned.
+    pub texture: Handle<Image>,
+    /// How fast the layer scrolls when the camera moves, see [`BackgroundMovementScale`].
+    pub movement_scale: Vec2,
+    /// Lines the layer up with the world, see [`BackgroundOffset`].
+    pub offset: BackgroundOffset,
+    /// Multiplied with the texture's color, including its alpha.
+    pub tint: LinearRgba,
+    /// How much each tile is scaled, see [`BackgroundTileScale`](crate::BackgroundTileScale).
+    pub tile_scale: Vec2,
+}
+
+impl BackgroundLayer {
+    /// A layer tiling `texture` that stays fixed in the world.
+    pub fn new(texture: Handle<Image>) -> Self {
+        Self {
+            texture,
+            ..default()
+        }
+    }
+
+    /// Scrolls the layer at `movement_scale` times the camera's speed.
+    pub fn with_movement_scale(
+        mut self,
+        movement_scale: impl Into<BackgroundMovementScale>,
+    ) -> Self {
+        self.movement_scale = Vec2::splat(movement_scale.into().scale);
+        self
+    }
+
+    /// Lines the layer up with the world, see [`BackgroundOffset`].
+    pub fn with_offset(mut self, offset: BackgroundOffset) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Multiplies the layer's color with `tint`.
+    pub fn with_tint(mut self, tint: impl Into<LinearRgba>) -> Self {
+        self.tint = tint.into();
+        self
+    }
+
+    /// Scales each tile of the layer, see [`BackgroundTileScale`](crate::BackgroundTileScale).
+    pub fn with_tile_scale(mut self, tile_scale: Vec2) -> Self {
+        self.tile_scale = tile_scale;
+        self
+    }
+}
+
+impl Default for BackgroundLayer {
+    fn default() -> Self {
+        Self {
+            texture: Handle::default(),
+            movement_scale: Vec2::ONE,
+            offset: BackgroundOffset::default(),
+            tint: LinearRgba::WHITE,
+            tile_scale: Vec2::ONE,
+        }
+    }
+}
+
+/// Draws up to [`MAX_BACKGROUND_LAYERS`] parallax layers in a single fullscreen pass, instead of
+/// one pass per layer like separate [`BackgroundMaterial`](crate::BackgroundMaterial) backgrounds.
+///
+/// Each layer has its own movement scale, offset, tint and tile scale. The layers are composited
+/// back to front, the first layer is the furthest back. The background's [`BackgroundOffset`],
+/// [`BackgroundScrollPosition`](crate::BackgroundScrollPosition) and
+/// [`BackgroundTint`](crate::BackgroundTint) apply to every layer, its [`BackgroundMovementScale`]
+/// and [`BackgroundMovementAxes`](crate::BackgroundMovementAxes) are ignored.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_tiling_background::{BackgroundLayer, LayeredBackgroundMaterial, TilingBackground};
+/// # fn setup(mut commands: Commands, asset_server: Res<AssetServer>, mut materials: ResMut<Assets<LayeredBackgroundMaterial>>) {
+/// commands.spawn((
+///     TilingBackground,
+///     MeshMaterial2d(materials.add(LayeredBackgroundMaterial::new([
+///         BackgroundLayer::new(asset_server.load("space_test.png")).with_movement_scale(0.5),
+///         BackgroundLayer::new(asset_server.load("space_dust_transparent.png")),
+///     ]))),
+/// ));
+/// # }
+/// ```
+#[derive(AsBindGroup, Debug, Clone, Asset, Reflect)]
+#[reflect(Default, Debug)]
+#[uniform(0, LayeredBackgroundUniform)]
+#[bind_group_data(BackgroundMaterialKey)]
+pub struct LayeredBackgroundMaterial {
+    /// The layers, back to front. Layers past [`MAX_BACKGROUND_LAYERS`] aren't drawn.
+    pub layers: Vec<BackgroundLayer>,
+    /// Fades the whole background, `0.0` is fully transparent and `1.0` leaves it unchanged.
+    pub opacity: f32,
+    /// See [`BackgroundScaling`], set by the plugin.
+    pub scaling: BackgroundScaling,
+    /// How the composited layers are blended with what is behind them.
+    pub blend_mode: BackgroundBlendMode,
+    // The images that are drawn for each layer, either its texture or the
+    // `BackgroundFallbackTexture` while it's loading.
+    #[texture(1)]
+    #[sampler(2)]
+    displayed_texture_0: Option<Handle<Image>>,
+    #[texture(3)]
+    #[sampler(4)]
+    displayed_texture_1: Option<Handle<Image>>,
+    #[texture(5)]
+    #[sampler(6)]
+    displayed_texture_2: Option<Handle<Image>>,
+    #[texture(7)]
+    #[sampler(8)]
+    displayed_texture_3: Option<Handle<Image>>,
+}
+
+impl LayeredBackgroundMaterial {
+    /// A material drawing `layers`, back to front.
+    pub fn new(layers: impl IntoIterator<Item = BackgroundLayer>) -> Self {
+        let mut material = Self {
+            layers: layers.into_iter().collect(),
+            ..default()
+        };
+        material.set_fallback(None);
+        material
+    }
+
+    /// Adds a layer in front of the others.
+    pub fn with_layer(mut self, layer: BackgroundLayer) -> Self {
+        self.layers.push(layer);
+        self.set_fallback(None);
+        self
+    }
+
+    /// The layers that are drawn.
+    fn drawn_layers(&self) -> &[BackgroundLayer] {
+        &self.layers[..self.layers.len().min(MAX_BACKGROUND_LAYERS)]
+    }
+}
+
+impl Default for LayeredBackgroundMaterial {
+    fn default() -> Self {
+        Self {
+            layers: Vec::new(),
+            opacity: 1.0,
+            scaling: BackgroundScaling::default(),
+            blend_mode: BackgroundBlendMode::default(),
+            displayed_texture_0: None,
+            displayed_texture_1: None,
+            displayed_texture_2: None,
+            displayed_texture_3: None,
+        }
+    }
+}
+
+impl Material2d for LayeredBackgroundMaterial {
+    fn vertex_shader() -> ShaderRef {
+        TILED_BG_SHADER_HANDLE.into()
+    }
+    fn fragment_shader() -> ShaderRef {
+        LAYERED_BG_SHADER_HANDLE.into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        self.blend_mode.alpha_mode()
+    }
+
+    fn specialize(
+        descriptor: &mut RenderPipelineDescriptor,
+        _: &MeshVertexBufferLayoutRef,
+        key: Material2dKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        key.bind_group_data
+            .blend_mode
+            .specialize_pipeline(descriptor);
+        Ok(())
+    }
+}
+
+impl From<&LayeredBackgroundMaterial> for BackgroundMaterialKey {
+    fn from(material: &LayeredBackgroundMaterial) -> Self {
+        Self {
+            blend_mode: material.blend_mode,
+        }
+    }
+}
+
+impl ScrollingBackground for LayeredBackgroundMaterial {
+    // Offset, scroll and tint are read per entity, the movement scale comes from each layer.
+    const PER_ENTITY_PARAMETERS: bool = true;
+
+    fn set_movement(&mut self, _movement: f32) {}
+
+    fn set_scaling(&mut self, scaling: BackgroundScaling) {
+        self.scaling = scaling;
+    }
+
+    fn textures(&self) -> Vec<Handle<Image>> {
+        self.drawn_layers()
+            .iter()
+            .map(|layer| layer.texture.clone())
+            .collect()
+    }
+
+    fn set_fallback(&mut self, fallback: Option<Handle<Image>>) {
+        let mut displayed = [None, None, None, None];
+        for (displayed, layer) in displayed.iter_mut().zip(self.drawn_layers()) {
+            *displayed = Some(fallback.clone().unwrap_or_else(|| layer.texture.clone()));
+        }
+        [
+            self.displayed_texture_0,
+            self.displayed_texture_1,
+            self.displayed_texture_2,
+            self.displayed_texture_3,
+        ] = displayed;
+    }
+}
+
+impl AsBindGroupShaderType<LayeredBackgroundUniform> for LayeredBackgroundMaterial {
+    fn as_bind_group_shader_type(
+        &self,
+        _images: &RenderAssets<GpuImage>,
+    ) -> LayeredBackgroundUniform {
+        let mut layers = [BackgroundLayerUniform::default(); MAX_BACKGROUND_LAYERS];
+        for (uniform, layer) in layers.iter_mut().zip(self.drawn_layers()) {
+            *uniform = BackgroundLayerUniform {
+                tint: layer.tint.to_vec4(),
+                movement_scale: layer.movement_scale,
+                parallax_origin: layer.offset.parallax_origin,
+                offset: layer.offset.offset,
+                tile_scale: layer.tile_scale,
+            };
+        }
+        LayeredBackgroundUniform {
+            layers,
+            layer_count: self.drawn_layers().len() as u32,
+            scaling: self.scaling.shader_value(),
+            opacity: self.opacity,
+        }
+    }
+}
+
+// Newer compilers warn about the size checks `ShaderType` generates.
+#[allow(dead_code)]
+mod uniforms {
+    use bevy::prelude::*;
+    use bevy::render::render_resource::ShaderType;
+
+    use super::MAX_BACKGROUND_LAYERS;
+
+    /// The uniform of a single [`BackgroundLayer`](super::BackgroundLayer).
+    #[derive(ShaderType, Clone, Copy, Debug, Default)]
+    pub struct BackgroundLayerUniform {
+        pub tint: Vec4,
+        pub movement_scale: Vec2,
+        pub parallax_origin: Vec2,
+        pub offset: Vec2,
+        pub tile_scale: Vec2,
+    }
+
+    /// The uniform of a [`LayeredBackgroundMaterial`](super::LayeredBackgroundMaterial), its size is
+    /// rounded up to a multiple of 16 bytes for webgl2 since it contains structs.
+    #[derive(ShaderType, Clone, Copy, Debug)]
+    pub struct LayeredBackgroundUniform {
+        pub layers: [BackgroundLayerUniform; MAX_BACKGROUND_LAYERS],
+        pub layer_count: u32,
+        pub scaling: u32,
+        pub opacity: f32,
+    }
+}
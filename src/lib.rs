@@ -1,27 +1,59 @@
+use std::error::Error;
+use std::fmt;
 use std::hash::Hash;
 use std::marker::PhantomData;
+use std::sync::Arc;
 use std::sync::Once;
+use std::time::Duration;
 
 use bevy::app::{App, Plugin};
-use bevy::asset::{load_internal_asset, LoadState};
-use bevy::core_pipeline::fullscreen_vertex_shader::FULLSCREEN_SHADER_HANDLE;
+use bevy::asset::AssetPath;
+use bevy::asset::{load_internal_asset, AssetLoadError, LoadState};
 use bevy::ecs::world::Command;
-use bevy::image::{ImageAddressMode, ImageSampler, ImageSamplerDescriptor};
+use bevy::image::{ImageAddressMode, ImageLoaderSettings, ImageSampler, ImageSamplerDescriptor};
+use bevy::math::{Affine2, Affine3, Mat3, Mat3A};
 use bevy::prelude::*;
-use bevy::render::mesh::MeshVertexBufferLayoutRef;
+use bevy::render::mesh::{Indices, MeshVertexBufferLayoutRef, PrimitiveTopology};
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_phase::AddRenderCommand;
 use bevy::render::render_resource::{
-    AsBindGroup, PrimitiveState, RenderPipelineDescriptor, ShaderRef, SpecializedMeshPipelineError,
+    AddressMode, AsBindGroup, BlendComponent, BlendFactor, BlendOperation, BlendState, Extent3d,
+    RenderPipelineDescriptor, ShaderRef, SpecializedMeshPipelineError, TextureDimension,
+    TextureFormat,
 };
-use bevy::render::view::NoFrustumCulling;
-use bevy::sprite::{AlphaMode2d, Material2d, Material2dKey, Material2dPlugin};
-use bevy::window::{PrimaryWindow, WindowResized};
+use bevy::render::sync_world::MainEntity;
+use bevy::render::texture::TRANSPARENT_IMAGE_HANDLE;
+use bevy::render::view::{NoFrustumCulling, VisibilitySystems, VisibleEntities};
+use bevy::render::{Extract, ExtractSchedule, Render, RenderApp, RenderSet};
+use bevy::sprite::{
+    extract_mesh2d, AlphaMode2d, Material2d, Material2dKey, Material2dPlugin, RenderMesh2dInstances,
+};
+use bevy::utils::{HashMap, HashSet};
+
+mod bundles;
+mod layered;
+mod pass;
+
+#[allow(deprecated)]
+pub use bundles::{BackgroundImageBundle, CustomBackgroundImageBundle};
+pub use layered::{
+    BackgroundLayer, BackgroundLayerUniform, LayeredBackgroundMaterial, LayeredBackgroundUniform,
+    MAX_BACKGROUND_LAYERS,
+};
+use pass::{queue_background_pass, BackgroundPassPlugin, DrawBackground};
+pub use pass::{BackgroundPass, BackgroundPassLabel, BackgroundPhaseItem};
 
 pub const TILED_BG_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(429593476423978);
 
 pub const BGLIB_HANDLE: Handle<Shader> = Handle::weak_from_u128(429593476423988);
 
+pub const LAYERED_BG_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(429593476423998);
+
 pub const BG_MESH_HANDLE: Handle<Mesh> = Handle::weak_from_u128(12316584166263728426);
 
+/// A grey checkerboard, see [`BackgroundFallbackTexture::checkerboard`].
+pub const CHECKERBOARD_TEXTURE_HANDLE: Handle<Image> = Handle::weak_from_u128(12316584166263728427);
+
 /// Prevent shaders from being loaded multiple times, emitting events etc.
 pub static BEVY_TILING_PLUGIN_SHADERS_LOADED: Once = Once::new();
 
@@ -35,9 +67,68 @@ fn load_plugin_shadercode(app: &mut App) {
 
     load_internal_asset!(app, BGLIB_HANDLE, "shaders/bglib.wgsl", Shader::from_wgsl);
 
+    load_internal_asset!(
+        app,
+        LAYERED_BG_SHADER_HANDLE,
+        "shaders/layered.wgsl",
+        Shader::from_wgsl
+    );
+
     // This is doing the same thing as `load_internal_asset` just not from a file.
     let mut meshes = app.world_mut().resource_mut::<Assets<Mesh>>();
-    meshes.insert(&BG_MESH_HANDLE, Mesh::from(Rectangle::new(1., 1.)));
+    meshes.insert(&BG_MESH_HANDLE, background_triangle());
+
+    let mut images = app.world_mut().resource_mut::<Assets<Image>>();
+    images.insert(&CHECKERBOARD_TEXTURE_HANDLE, checkerboard_image());
+}
+
+/// A single triangle covering the whole screen, its positions are in clip space. The background
+/// shader's `vertex` entry point passes them on without a transform.
+fn background_triangle() -> Mesh {
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+    .with_inserted_attribute(
+        Mesh::ATTRIBUTE_POSITION,
+        vec![[-1.0, -1.0, 0.0], [3.0, -1.0, 0.0], [-1.0, 3.0, 0.0]],
+    )
+    .with_inserted_indices(Indices::U16(vec![0, 1, 2]))
+}
+
+/// A 16x16 image of 8x8 grey squares, sampled with nearest filtering so it stays sharp.
+fn checkerboard_image() -> Image {
+    const SIZE: u32 = 16;
+    const LIGHT: [u8; 4] = [160, 160, 160, 255];
+    const DARK: [u8; 4] = [96, 96, 96, 255];
+    let data = (0..SIZE * SIZE)
+        .flat_map(|i| {
+            let (x, y) = (i % SIZE, i / SIZE);
+            if (x / 8 + y / 8) % 2 == 0 {
+                LIGHT
+            } else {
+                DARK
+            }
+        })
+        .collect();
+    let mut image = Image::new(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+        address_mode_u: ImageAddressMode::Repeat,
+        address_mode_v: ImageAddressMode::Repeat,
+        address_mode_w: ImageAddressMode::Repeat,
+        ..ImageSamplerDescriptor::nearest()
+    });
+    image
 }
 
 /// Bevy plugin for tiling backgrounds.
@@ -58,17 +149,187 @@ where
             load_plugin_shadercode(app);
         });
 
+        // Shared by every material's plugin.
+        if !app.is_plugin_added::<RepeatingImagesPlugin>() {
+            app.add_plugins(RepeatingImagesPlugin);
+        }
+
         app.add_plugins(Material2dPlugin::<T>::default())
             .register_type::<BackgroundMovementScale>()
-            .insert_resource(UpdateSamplerRepeating::default())
-            .add_systems(PostUpdate, Self::on_window_resize)
-            .add_systems(Update, Self::on_background_added)
-            .add_systems(Update, Self::queue_update_sampler)
-            .add_systems(Update, Self::update_movement_scale_system)
-            .add_systems(Update, update_sampler_on_loaded_system);
+            .register_type::<BackgroundMovementAxes>()
+            .register_type::<TilingBackground>()
+            .register_type::<BackgroundScaling>()
+            .register_type::<BackgroundCamera>()
+            .register_type::<BackgroundScrollVelocity>()
+            .register_type::<BackgroundScrollPosition>()
+            .register_type::<BackgroundTileScale>()
+            .register_type::<BackgroundOffset>()
+            .register_type::<BackgroundUvTransform>()
+            .register_type::<BackgroundAddressMode>()
+            .register_type::<BackgroundStrip>()
+            .register_type::<BackgroundPixelSnap>()
+            .register_type::<DefaultBackgroundPixelSnap>()
+            .register_type::<BackgroundFlipbook>()
+            .register_type::<BackgroundLoadState>()
+            .register_type::<BackgroundFallbackTexture>()
+            .register_type::<BackgroundTint>()
+            .register_type::<BackgroundBlendMode>()
+            .register_asset_reflect::<BackgroundMaterial>()
+            .register_asset_reflect::<LayeredBackgroundMaterial>()
+            .add_event::<BackgroundReady>()
+            .add_event::<BackgroundLoadFailed>()
+            .init_resource::<BackgroundFallbackTexture>()
+            // Backgrounds spawned without `TilingBackground` are tracked too, so their samplers and
+            // displayed texture are set.
+            .register_required_components::<MeshMaterial2d<T>, BackgroundLoadState>()
+            .init_resource::<DefaultBackgroundPixelSnap>()
+            .add_systems(
+                PostUpdate,
+                Self::filter_background_camera_visibility.after(VisibilitySystems::CheckVisibility),
+            )
+            .add_systems(Update, Self::update_scaling_system)
+            .add_systems(Update, Self::update_tile_scale_system)
+            .add_systems(Update, Self::update_uv_transform_system)
+            .add_systems(Update, Self::update_address_mode_system)
+            .add_systems(Update, Self::update_strip_system)
+            .add_systems(Update, Self::update_pixel_snap_system)
+            .add_systems(Update, Self::scroll_velocity_system)
+            .add_systems(
+                Update,
+                (
+                    Self::unshare_flipbook_materials_system,
+                    Self::flipbook_system,
+                    Self::update_flipbook_system,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    Self::queue_update_sampler.before(TilingBackgroundSystems::UpdateSamplers),
+                    (Self::update_load_state_system, Self::update_fallback_system)
+                        .chain()
+                        .after(TilingBackgroundSystems::UpdateSamplers),
+                ),
+            );
+
+        // Materials reading these per entity aren't modified, so backgrounds sharing them don't
+        // overwrite each other.
+        if !T::PER_ENTITY_PARAMETERS {
+            app.add_systems(
+                Update,
+                (
+                    Self::update_movement_scale_system,
+                    Self::update_offset_system,
+                    Self::update_tint_system,
+                    Self::update_scroll_system.after(Self::scroll_velocity_system),
+                ),
+            );
+        }
+
+        if !app.is_plugin_added::<BackgroundPassPlugin>() {
+            app.add_plugins(BackgroundPassPlugin);
+        }
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .add_render_command::<BackgroundPhaseItem, DrawBackground<T>>()
+                .add_systems(
+                    Render,
+                    queue_background_pass::<T>.in_set(RenderSet::QueueMeshes),
+                );
+            if T::PER_ENTITY_PARAMETERS {
+                render_app.add_systems(
+                    ExtractSchedule,
+                    extract_background_instances::<T>.after(extract_mesh2d),
+                );
+            }
+        }
+    }
+}
+
+/// Backgrounds are drawn fullscreen and don't need their transform on the GPU, so for materials
+/// with [`ScrollingBackground::PER_ENTITY_PARAMETERS`] the instance transform Bevy uploads for
+/// every 2d mesh carries the background's movement scale, offset, scroll position and tint instead.
+/// Only its z translation is kept, which sorts the backgrounds.
+///
+/// See [`pack_background_instance`] for the layout.
+#[allow(clippy::type_complexity)]
+fn extract_background_instances<T: Material2d>(
+    mut render_mesh_instances: ResMut<RenderMesh2dInstances>,
+    backgrounds: Extract<
+        Query<
+            (
+                Entity,
+                Option<&BackgroundMovementScale>,
+                Option<&BackgroundMovementAxes>,
+                Option<&BackgroundOffset>,
+                Option<&BackgroundScrollPosition>,
+                Option<&BackgroundTint>,
+            ),
+            With<MeshMaterial2d<T>>,
+        >,
+    >,
+) {
+    for (entity, scale, axes, offset, scroll, tint) in backgrounds.iter() {
+        // Backgrounds that aren't visible aren't extracted.
+        let Some(instance) = render_mesh_instances.get_mut(&MainEntity::from(entity)) else {
+            continue;
+        };
+        let offset = offset.copied().unwrap_or_default();
+        pack_background_instance(
+            &mut instance.transforms.world_from_local,
+            movement_scale(scale, axes),
+            offset.parallax_origin,
+            offset.offset + scroll.map_or(Vec2::ZERO, |scroll| scroll.0),
+            tint.copied().unwrap_or_default().0,
+        );
     }
 }
 
+/// Packs the parameters of a background into its transform, keeping the z translation:
+///
+/// | column        | x               | y                 | z                 |
+/// |---------------|-----------------|-------------------|-------------------|
+/// | `x_axis`      | movement x      | parallax origin x | parallax origin y |
+/// | `y_axis`      | offset x        | movement y        | offset y          |
+/// | `z_axis`      | tint red        | tint green        | tint blue         |
+/// | `translation` | tint alpha      | diagonal shift    | z                 |
+///
+/// Bevy inverts the 3x3 matrix, so [`invertible_shift`] is added to its diagonal. Only the movement
+/// scale and the blue tint sit on the diagonal, so large offsets and parallax origins keep their
+/// precision. Unpacked by `braymatter::bglib::background_instance`.
+fn pack_background_instance(
+    packed: &mut Affine3,
+    movement_scale: Vec2,
+    parallax_origin: Vec2,
+    offset: Vec2,
+    tint: LinearRgba,
+) {
+    let matrix3 = Mat3::from_cols(
+        Vec3::new(movement_scale.x, parallax_origin.x, parallax_origin.y),
+        Vec3::new(offset.x, movement_scale.y, offset.y),
+        Vec3::new(tint.red, tint.green, tint.blue),
+    );
+    let diagonal_shift = invertible_shift(matrix3);
+    packed.matrix3 = matrix3 + Mat3::from_diagonal(Vec3::splat(diagonal_shift));
+    packed.translation.x = tint.alpha;
+    packed.translation.y = diagonal_shift;
+}
+
+/// The smallest whole number that keeps the determinant of `matrix` at least `0.5` away from zero
+/// when it's added to its diagonal, so its inverse stays finite. The determinant is a cubic in the
+/// shift whose third difference is `6`, so one of `0..4` always works.
+fn invertible_shift(matrix: Mat3) -> f32 {
+    (0..4)
+        .map(|shift| shift as f32)
+        .find(|shift| {
+            // Computed the same way `Affine3A::inverse` does.
+            let shifted = Mat3A::from(matrix + Mat3::from_diagonal(Vec3::splat(*shift)));
+            shifted.determinant().abs() >= 0.5
+        })
+        .unwrap_or_default()
+}
+
 impl<T: Material2d + AsBindGroup + Clone + ScrollingBackground> TilingBackgroundPlugin<T>
 where
     <T as AsBindGroup>::Data: Clone + Eq + Send + Sync + Clone + Sized + Hash,
@@ -79,275 +340,2258 @@ where
         }
     }
 
-    pub fn on_window_resize(
-        mut events: EventReader<WindowResized>,
-        mut backgrounds: Query<&mut Transform, With<MeshMaterial2d<T>>>,
+    /// Does nothing, backgrounds cover their camera's viewport without being resized.
+    #[deprecated(
+        since = "0.13.0",
+        note = "backgrounds are drawn with a fullscreen triangle and don't need to be resized"
+    )]
+    pub fn on_window_resize() {}
+
+    /// Does nothing, backgrounds cover their camera's viewport without being resized.
+    #[deprecated(
+        since = "0.13.0",
+        note = "backgrounds are drawn with a fullscreen triangle and don't need to be resized"
+    )]
+    pub fn on_background_added() {}
+
+    /// Hides backgrounds with a [`BackgroundCamera`] from every camera but that one.
+    pub fn filter_background_camera_visibility(
+        mut cameras: Query<(Entity, &mut VisibleEntities), With<Camera>>,
+        backgrounds: Query<&BackgroundCamera, With<MeshMaterial2d<T>>>,
+    ) {
+        if backgrounds.is_empty() {
+            return;
+        }
+
+        for (camera, mut visible_entities) in cameras.iter_mut() {
+            let visible_from_camera =
+                |entity: &Entity| backgrounds.get(*entity).map_or(true, |bg| bg.0 == camera);
+            visible_entities
+                .get_mut::<With<Mesh2d>>()
+                .retain(visible_from_camera);
+            // Backgrounds drawn in the `BackgroundPass`.
+            visible_entities
+                .get_mut::<With<TilingBackground>>()
+                .retain(visible_from_camera);
+        }
+    }
+
+    /// Queues the [`textures`](ScrollingBackground::textures) of backgrounds for a repeating
+    /// sampler when they are spawned, when they switch materials and when the textures of their
+    /// material change. Uses the background's [`BackgroundAddressMode`] if it has one.
+    ///
+    /// Backgrounds go back to [`BackgroundLoadState::Loading`] when their textures change.
+    #[allow(clippy::type_complexity)]
+    fn queue_update_sampler(
+        mut material_events: EventReader<AssetEvent<T>>,
+        mut backgrounds: Query<(
+            Ref<MeshMaterial2d<T>>,
+            Option<&BackgroundAddressMode>,
+            &mut BackgroundLoadState,
+        )>,
+        materials: Res<Assets<T>>,
+        mut queued_textures: Local<HashMap<AssetId<T>, Vec<AssetId<Image>>>>,
+        mut repeating_images: ResMut<RepeatingImages>,
     ) {
-        events.read().for_each(|ev| {
-            for mut transform in backgrounds.iter_mut() {
-                transform.scale.x = ev.width;
-                transform.scale.y = ev.height;
+        let mut changed_materials = HashSet::new();
+        for event in material_events.read() {
+            match event {
+                AssetEvent::Added { id }
+                | AssetEvent::Modified { id }
+                | AssetEvent::LoadedWithDependencies { id } => {
+                    changed_materials.insert(*id);
+                }
+                AssetEvent::Removed { id } | AssetEvent::Unused { id } => {
+                    queued_textures.remove(id);
+                }
             }
-        });
+        }
+
+        // Materials whose textures changed this frame, so every background using them is updated.
+        let mut retextured_materials = HashSet::new();
+        for (material_handle, address_mode, mut load_state) in backgrounds.iter_mut() {
+            let id = material_handle.id();
+            if !material_handle.is_changed() && !changed_materials.contains(&id) {
+                continue;
+            }
+            if material_handle.is_changed() {
+                load_state.set_if_neq(BackgroundLoadState::Loading);
+            }
+            let Some(material) = materials.get(id) else {
+                continue;
+            };
+            let texture_ids = material
+                .textures()
+                .iter()
+                .map(Handle::id)
+                .collect::<Vec<_>>();
+            // Materials are modified every time a background scrolls, only queue actual changes.
+            if !retextured_materials.contains(&id) && queued_textures.get(&id) == Some(&texture_ids)
+            {
+                continue;
+            }
+            load_state.set_if_neq(BackgroundLoadState::Loading);
+            let address_mode = address_mode.copied().unwrap_or_default();
+            for texture_id in &texture_ids {
+                repeating_images.insert(*texture_id, address_mode);
+            }
+            queued_textures.insert(id, texture_ids);
+            retextured_materials.insert(id);
+        }
     }
 
-    pub fn on_background_added(
-        windows: Query<&Window, With<PrimaryWindow>>,
-        mut backgrounds: Query<&mut Transform, Added<MeshMaterial2d<T>>>,
+    /// Marks loading backgrounds as ready once their material and textures are loaded and the
+    /// samplers of their textures are set, sending [`BackgroundReady`]. Sends
+    /// [`BackgroundLoadFailed`] instead if one of their textures failed to load.
+    ///
+    /// Failed backgrounds start loading again when one of their textures is loaded after all, e.g.
+    /// by hot reloading a fixed file or by adding the image at runtime.
+    #[allow(clippy::too_many_arguments)]
+    fn update_load_state_system(
+        mut image_events: EventReader<AssetEvent<Image>>,
+        asset_server: Res<AssetServer>,
+        materials: Res<Assets<T>>,
+        images: Res<Assets<Image>>,
+        repeating_images: Res<RepeatingImages>,
+        mut backgrounds: Query<(Entity, &MeshMaterial2d<T>, &mut BackgroundLoadState)>,
+        mut ready_events: EventWriter<BackgroundReady>,
+        mut failed_events: EventWriter<BackgroundLoadFailed>,
     ) {
-        if let Ok(window) = windows.get_single() {
-            for mut transform in backgrounds.iter_mut() {
-                transform.scale.x = window.width();
-                transform.scale.y = window.height();
+        let loaded_images = image_events
+            .read()
+            .filter_map(|event| match event {
+                AssetEvent::Added { id }
+                | AssetEvent::Modified { id }
+                | AssetEvent::LoadedWithDependencies { id } => Some(*id),
+                AssetEvent::Removed { .. } | AssetEvent::Unused { .. } => None,
+            })
+            .collect::<HashSet<_>>();
+
+        for (entity, material_handle, mut load_state) in backgrounds.iter_mut() {
+            let Some(material) = materials.get(&**material_handle) else {
+                continue;
+            };
+            if *load_state == BackgroundLoadState::Failed
+                && material
+                    .textures()
+                    .iter()
+                    .any(|texture| loaded_images.contains(&texture.id()))
+            {
+                *load_state = BackgroundLoadState::Loading;
             }
-        };
+            if *load_state != BackgroundLoadState::Loading {
+                continue;
+            }
+
+            let mut ready = true;
+            for texture in material.textures() {
+                // The asset server keeps reporting a failure for images that were added at runtime
+                // in place of the failed file.
+                let failed = match asset_server.get_load_state(&texture) {
+                    Some(LoadState::Failed(error)) if !images.contains(&texture) => Some(error),
+                    _ => None,
+                };
+                if let Some(error) = failed {
+                    let error = TilingBackgroundError::TextureLoadFailed(error);
+                    warn!("Background {entity}: {error}");
+                    *load_state = BackgroundLoadState::Failed;
+                    failed_events.send(BackgroundLoadFailed {
+                        entity,
+                        handle: texture,
+                        error,
+                    });
+                    break;
+                }
+                // Images added at runtime aren't known to the asset server, so check the image
+                // itself instead of its load state.
+                let sampler_set = images.get(&texture).is_some_and(|image| {
+                    repeating_images
+                        .address_modes
+                        .get(&texture.id())
+                        .is_none_or(|address_mode| {
+                            sampler_has_address_mode(&image.sampler, *address_mode)
+                        })
+                });
+                ready &= sampler_set;
+            }
+
+            if ready && *load_state == BackgroundLoadState::Loading {
+                *load_state = BackgroundLoadState::Ready;
+                ready_events.send(BackgroundReady { entity });
+            }
+        }
     }
 
-    fn queue_update_sampler(
-        query: Query<&Sprite, Added<MeshMaterial2d<T>>>,
-        mut update_samplers: ResMut<UpdateSamplerRepeating>,
+    /// Shows the [`BackgroundFallbackTexture`] on backgrounds that are loading or failed to load,
+    /// and their own textures once they are ready.
+    fn update_fallback_system(
+        fallback: Res<BackgroundFallbackTexture>,
+        query: Query<(&MeshMaterial2d<T>, Ref<BackgroundLoadState>)>,
+        mut background_materials: ResMut<Assets<T>>,
     ) {
-        for sprite in query.iter() {
-            update_samplers.0.push(sprite.image.clone());
+        for (bg_material_handle, load_state) in query.iter() {
+            if !fallback.is_changed() && !load_state.is_changed() {
+                continue;
+            }
+            let shown_fallback = match *load_state {
+                BackgroundLoadState::Ready => None,
+                BackgroundLoadState::Loading | BackgroundLoadState::Failed => fallback.0.clone(),
+            };
+            if let Some(background_material) = background_materials.get_mut(&**bg_material_handle) {
+                background_material.set_fallback(shown_fallback);
+            }
         }
     }
 
+    /// Applies each background's [`BackgroundMovementScale`] and [`BackgroundMovementAxes`], and
+    /// recomputes the movement of backgrounds either was removed from.
+    #[allow(clippy::type_complexity)]
     pub fn update_movement_scale_system(
+        query: Query<
+            (
+                &MeshMaterial2d<T>,
+                Option<&BackgroundMovementScale>,
+                Option<&BackgroundMovementAxes>,
+            ),
+            (
+                Or<(With<BackgroundMovementScale>, With<BackgroundMovementAxes>)>,
+                Or<(
+                    Changed<BackgroundMovementScale>,
+                    Changed<BackgroundMovementAxes>,
+                    Changed<MeshMaterial2d<T>>,
+                )>,
+            ),
+        >,
+        mut removed_scales: RemovedComponents<BackgroundMovementScale>,
+        mut removed_axes: RemovedComponents<BackgroundMovementAxes>,
+        backgrounds: Query<(
+            &MeshMaterial2d<T>,
+            Option<&BackgroundMovementScale>,
+            Option<&BackgroundMovementAxes>,
+        )>,
+        mut background_materials: ResMut<Assets<T>>,
+    ) {
+        let removed = removed_scales.read().chain(removed_axes.read());
+        for (bg_material_handle, scale, axes) in query.iter().chain(backgrounds.iter_many(removed))
+        {
+            if let Some(background_material) = background_materials.get_mut(&**bg_material_handle) {
+                background_material.set_movement_axes(movement_scale(scale, axes));
+            }
+        }
+    }
+
+    /// Applies each background's [`BackgroundScaling`], and resets backgrounds it was removed from.
+    #[allow(clippy::type_complexity)]
+    pub fn update_scaling_system(
+        query: Query<
+            (&MeshMaterial2d<T>, &BackgroundScaling),
+            Or<(Changed<BackgroundScaling>, Changed<MeshMaterial2d<T>>)>,
+        >,
+        removed: RemovedComponents<BackgroundScaling>,
+        backgrounds: Query<&MeshMaterial2d<T>>,
+        mut background_materials: ResMut<Assets<T>>,
+    ) {
+        for (bg_material_handle, scaling) in query.iter() {
+            if let Some(background_material) = background_materials.get_mut(&**bg_material_handle) {
+                background_material.set_scaling(*scaling);
+            }
+        }
+        reset_removed(
+            removed,
+            &backgrounds,
+            &mut background_materials,
+            |material| material.set_scaling(BackgroundScaling::default()),
+        );
+    }
+
+    /// Applies each background's [`BackgroundTileScale`], and resets backgrounds it was removed from.
+    #[allow(clippy::type_complexity)]
+    pub fn update_tile_scale_system(
+        query: Query<
+            (&MeshMaterial2d<T>, &BackgroundTileScale),
+            Or<(Changed<BackgroundTileScale>, Changed<MeshMaterial2d<T>>)>,
+        >,
+        removed: RemovedComponents<BackgroundTileScale>,
+        backgrounds: Query<&MeshMaterial2d<T>>,
+        mut background_materials: ResMut<Assets<T>>,
+    ) {
+        for (bg_material_handle, tile_scale) in query.iter() {
+            if let Some(background_material) = background_materials.get_mut(&**bg_material_handle) {
+                background_material.set_tile_scale(tile_scale.0);
+            }
+        }
+        reset_removed(
+            removed,
+            &backgrounds,
+            &mut background_materials,
+            |material| material.set_tile_scale(BackgroundTileScale::default().0),
+        );
+    }
+
+    /// Applies each background's [`BackgroundOffset`], and resets backgrounds it was removed from.
+    #[allow(clippy::type_complexity)]
+    pub fn update_offset_system(
+        query: Query<
+            (&MeshMaterial2d<T>, &BackgroundOffset),
+            Or<(Changed<BackgroundOffset>, Changed<MeshMaterial2d<T>>)>,
+        >,
+        removed: RemovedComponents<BackgroundOffset>,
+        backgrounds: Query<&MeshMaterial2d<T>>,
+        mut background_materials: ResMut<Assets<T>>,
+    ) {
+        for (bg_material_handle, offset) in query.iter() {
+            if let Some(background_material) = background_materials.get_mut(&**bg_material_handle) {
+                background_material.set_offset(*offset);
+            }
+        }
+        reset_removed(
+            removed,
+            &backgrounds,
+            &mut background_materials,
+            |material| material.set_offset(BackgroundOffset::default()),
+        );
+    }
+
+    /// Applies each background's [`BackgroundTint`], and resets backgrounds it was removed from.
+    #[allow(clippy::type_complexity)]
+    pub fn update_tint_system(
+        query: Query<
+            (&MeshMaterial2d<T>, &BackgroundTint),
+            Or<(Changed<BackgroundTint>, Changed<MeshMaterial2d<T>>)>,
+        >,
+        removed: RemovedComponents<BackgroundTint>,
+        backgrounds: Query<&MeshMaterial2d<T>>,
+        mut background_materials: ResMut<Assets<T>>,
+    ) {
+        for (bg_material_handle, tint) in query.iter() {
+            if let Some(background_material) = background_materials.get_mut(&**bg_material_handle) {
+                background_material.set_tint(tint.0);
+            }
+        }
+        reset_removed(
+            removed,
+            &backgrounds,
+            &mut background_materials,
+            |material| material.set_tint(BackgroundTint::default().0),
+        );
+    }
+
+    /// Applies each background's [`BackgroundUvTransform`], and resets backgrounds it was removed from.
+    #[allow(clippy::type_complexity)]
+    pub fn update_uv_transform_system(
+        query: Query<
+            (&MeshMaterial2d<T>, &BackgroundUvTransform),
+            Or<(Changed<BackgroundUvTransform>, Changed<MeshMaterial2d<T>>)>,
+        >,
+        removed: RemovedComponents<BackgroundUvTransform>,
+        backgrounds: Query<&MeshMaterial2d<T>>,
+        mut background_materials: ResMut<Assets<T>>,
+    ) {
+        for (bg_material_handle, uv_transform) in query.iter() {
+            if let Some(background_material) = background_materials.get_mut(&**bg_material_handle) {
+                background_material.set_uv_transform(uv_transform.0);
+            }
+        }
+        reset_removed(
+            removed,
+            &backgrounds,
+            &mut background_materials,
+            |material| material.set_uv_transform(BackgroundUvTransform::default().0),
+        );
+    }
+
+    /// Applies each background's [`BackgroundAddressMode`], and resets backgrounds it was removed from.
+    #[allow(clippy::type_complexity)]
+    pub fn update_address_mode_system(
+        query: Query<
+            (&MeshMaterial2d<T>, &BackgroundAddressMode),
+            Or<(Changed<BackgroundAddressMode>, Changed<MeshMaterial2d<T>>)>,
+        >,
+        removed: RemovedComponents<BackgroundAddressMode>,
+        backgrounds: Query<&MeshMaterial2d<T>>,
+        mut background_materials: ResMut<Assets<T>>,
+    ) {
+        for (bg_material_handle, address_mode) in query.iter() {
+            if let Some(background_material) = background_materials.get_mut(&**bg_material_handle) {
+                background_material.set_address_mode(*address_mode);
+            }
+        }
+        reset_removed(
+            removed,
+            &backgrounds,
+            &mut background_materials,
+            |material| material.set_address_mode(BackgroundAddressMode::default()),
+        );
+    }
+
+    /// Applies each background's [`BackgroundStrip`], and resets backgrounds it was removed from.
+    #[allow(clippy::type_complexity)]
+    pub fn update_strip_system(
+        query: Query<
+            (&MeshMaterial2d<T>, &BackgroundStrip),
+            Or<(Changed<BackgroundStrip>, Changed<MeshMaterial2d<T>>)>,
+        >,
+        removed: RemovedComponents<BackgroundStrip>,
+        backgrounds: Query<&MeshMaterial2d<T>>,
+        mut background_materials: ResMut<Assets<T>>,
+    ) {
+        for (bg_material_handle, strip) in query.iter() {
+            if let Some(background_material) = background_materials.get_mut(&**bg_material_handle) {
+                background_material.set_strip(*strip);
+            }
+        }
+        reset_removed(
+            removed,
+            &backgrounds,
+            &mut background_materials,
+            |material| material.set_strip(BackgroundStrip::default()),
+        );
+    }
+
+    /// Applies each background's [`BackgroundPixelSnap`], falling back to
+    /// [`DefaultBackgroundPixelSnap`] for backgrounds without one.
+    #[allow(clippy::type_complexity)]
+    pub fn update_pixel_snap_system(
+        default_snap: Res<DefaultBackgroundPixelSnap>,
+        query: Query<(
+            Entity,
+            Ref<MeshMaterial2d<T>>,
+            Option<Ref<BackgroundPixelSnap>>,
+        )>,
+        mut removed: RemovedComponents<BackgroundPixelSnap>,
+        mut background_materials: ResMut<Assets<T>>,
+    ) {
+        let removed = removed.read().collect::<HashSet<_>>();
+        for (entity, bg_material_handle, snap) in query.iter() {
+            let snap_changed = snap.as_ref().is_some_and(|snap| snap.is_changed());
+            if !default_snap.is_changed()
+                && !snap_changed
+                && !bg_material_handle.is_changed()
+                && !removed.contains(&entity)
+            {
+                continue;
+            }
+            let mut snap = snap.map_or(default_snap.0, |snap| *snap);
+            if let BackgroundPixelSnap::Grid(size) = snap {
+                if !(size.is_finite() && size > 0.0) {
+                    warn!(
+                        "Background {entity}: pixel grid size {size} isn't positive, not snapping"
+                    );
+                    snap = BackgroundPixelSnap::Off;
+                }
+            }
+            if let Some(background_material) = background_materials.get_mut(&**bg_material_handle) {
+                background_material.set_pixel_snap(snap);
+            }
+        }
+    }
+
+    /// Advances [`BackgroundScrollPosition`] by [`BackgroundScrollVelocity`] using [`Time`], so it
+    /// follows virtual time and stops while the game is paused.
+    pub fn scroll_velocity_system(
+        time: Res<Time>,
         mut query: Query<
-            (&mut MeshMaterial2d<T>, &BackgroundMovementScale),
-            Changed<BackgroundMovementScale>,
+            (&BackgroundScrollVelocity, &mut BackgroundScrollPosition),
+            With<MeshMaterial2d<T>>,
+        >,
+    ) {
+        for (velocity, mut position) in query.iter_mut() {
+            if velocity.0 != Vec2::ZERO {
+                position.0 += velocity.0 * time.delta_secs();
+            }
+        }
+    }
+
+    /// Applies each background's [`BackgroundScrollPosition`], and resets backgrounds it was
+    /// removed from.
+    #[allow(clippy::type_complexity)]
+    pub fn update_scroll_system(
+        query: Query<
+            (&MeshMaterial2d<T>, &BackgroundScrollPosition),
+            Or<(
+                Changed<BackgroundScrollPosition>,
+                Changed<MeshMaterial2d<T>>,
+            )>,
         >,
+        removed: RemovedComponents<BackgroundScrollPosition>,
+        backgrounds: Query<&MeshMaterial2d<T>>,
+        mut background_materials: ResMut<Assets<T>>,
+    ) {
+        for (bg_material_handle, position) in query.iter() {
+            if let Some(background_material) = background_materials.get_mut(&**bg_material_handle) {
+                background_material.set_scroll(position.0);
+            }
+        }
+        reset_removed(
+            removed,
+            &backgrounds,
+            &mut background_materials,
+            |material| material.set_scroll(Vec2::ZERO),
+        );
+    }
+
+    /// Gives backgrounds with a [`BackgroundFlipbook`] a copy of their material when other
+    /// backgrounds share it, since the current frame is stored in the material.
+    pub fn unshare_flipbook_materials_system(
+        mut backgrounds: Query<(&mut MeshMaterial2d<T>, Option<Ref<BackgroundFlipbook>>)>,
+        mut background_materials: ResMut<Assets<T>>,
+    ) {
+        let mut changed = false;
+        let mut users = HashMap::<AssetId<T>, usize>::new();
+        for (material_handle, flipbook) in backgrounds.iter_mut() {
+            changed |= material_handle.is_changed()
+                || flipbook.is_some_and(|flipbook| flipbook.is_added());
+            *users.entry(material_handle.id()).or_default() += 1;
+        }
+        if !changed {
+            return;
+        }
+
+        for (mut material_handle, flipbook) in backgrounds.iter_mut() {
+            if flipbook.is_none() {
+                continue;
+            }
+            let id = material_handle.id();
+            let Some(users) = users.get_mut(&id).filter(|users| **users > 1) else {
+                continue;
+            };
+            let Some(material) = background_materials.get(id).cloned() else {
+                continue;
+            };
+            *users -= 1;
+            material_handle.0 = background_materials.add(material);
+        }
+    }
+
+    /// Advances each [`BackgroundFlipbook`] to its next frame using [`Time`].
+    pub fn flipbook_system(
+        time: Res<Time>,
+        layouts: Res<Assets<TextureAtlasLayout>>,
+        mut query: Query<&mut BackgroundFlipbook, With<MeshMaterial2d<T>>>,
+    ) {
+        for mut flipbook in query.iter_mut() {
+            let Some(count) = flipbook.frame_count(&layouts).filter(|count| *count > 0) else {
+                continue;
+            };
+            // Only mark the flipbook as changed when its frame does, so materials aren't updated
+            // every frame.
+            let timer = &mut flipbook.bypass_change_detection().timer;
+            timer.tick(time.delta());
+            let frames_passed = timer.times_finished_this_tick() as usize;
+            if frames_passed > 0 {
+                flipbook.frame = (flipbook.frame + frames_passed) % count;
+            }
+        }
+    }
+
+    /// Shows the current frame of each [`BackgroundFlipbook`], and the whole texture again on
+    /// backgrounds it was removed from.
+    pub fn update_flipbook_system(
+        layouts: Res<Assets<TextureAtlasLayout>>,
+        query: Query<(Ref<MeshMaterial2d<T>>, Ref<BackgroundFlipbook>)>,
+        removed: RemovedComponents<BackgroundFlipbook>,
+        backgrounds: Query<&MeshMaterial2d<T>>,
         mut background_materials: ResMut<Assets<T>>,
     ) {
-        for (bg_material_handle, scale) in query.iter_mut() {
-            if let Some(background_material) = background_materials.get_mut(&*bg_material_handle) {
-                background_material.set_movement(scale.scale);
+        for (bg_material_handle, flipbook) in query.iter() {
+            // Atlas layouts might not exist yet when the flipbook is added.
+            if !flipbook.is_changed() && !bg_material_handle.is_changed() && !layouts.is_changed() {
+                continue;
+            }
+            let Some(frame_rect) = flipbook.frame_rect(&layouts) else {
+                continue;
+            };
+            if let Some(background_material) = background_materials.get_mut(&**bg_material_handle) {
+                background_material.set_frame_rect(frame_rect);
             }
         }
+        reset_removed(
+            removed,
+            &backgrounds,
+            &mut background_materials,
+            |material| {
+                material.set_frame_rect(Rect::new(0.0, 0.0, 1.0, 1.0));
+            },
+        );
+    }
+}
+
+/// Calls `reset` on the materials of backgrounds whose `C` was removed. Backgrounds that were
+/// despawned are skipped.
+fn reset_removed<T: Material2d, C: Component>(
+    mut removed: RemovedComponents<C>,
+    backgrounds: &Query<&MeshMaterial2d<T>>,
+    materials: &mut Assets<T>,
+    reset: impl Fn(&mut T),
+) {
+    for entity in removed.read() {
+        let Ok(material_handle) = backgrounds.get(entity) else {
+            continue;
+        };
+        if let Some(material) = materials.get_mut(&**material_handle) {
+            reset(material);
+        }
     }
 }
 
 pub trait ScrollingBackground {
+    /// Whether the material's shader reads the movement scale, offset, scroll position and tint of
+    /// each background itself, with `braymatter::bglib::background_instance`. The plugin doesn't
+    /// call [`set_movement_axes`](Self::set_movement_axes), [`set_offset`](Self::set_offset),
+    /// [`set_scroll`](Self::set_scroll) and [`set_tint`](Self::set_tint) then, so one material can
+    /// be shared by layers with different parameters. `false` by default.
+    const PER_ENTITY_PARAMETERS: bool = false;
+
     ///Use this as a hook to set the materials movement scale if applicable to your shader.
     fn set_movement(&mut self, movement: f32);
+
+    /// Use this as a hook to set a separate movement scale for each axis, see
+    /// [`BackgroundMovementAxes`]. Calls [`set_movement`](Self::set_movement) with the x factor by
+    /// default.
+    fn set_movement_axes(&mut self, movement: Vec2) {
+        self.set_movement(movement.x);
+    }
+
+    /// Use this as a hook to set how the material's texels map onto the screen, see
+    /// [`BackgroundScaling`]. Does nothing by default.
+    fn set_scaling(&mut self, _scaling: BackgroundScaling) {}
+
+    /// Use this as a hook to set how far the material has scrolled on its own, in world units, see
+    /// [`BackgroundScrollVelocity`]. Does nothing by default.
+    fn set_scroll(&mut self, _scroll: Vec2) {}
+
+    /// Use this as a hook to set how much the material's tiles are scaled, see
+    /// [`BackgroundTileScale`]. Does nothing by default.
+    fn set_tile_scale(&mut self, _tile_scale: Vec2) {}
+
+    /// Use this as a hook to set the material's static offset and parallax origin, see
+    /// [`BackgroundOffset`]. Does nothing by default.
+    fn set_offset(&mut self, _offset: BackgroundOffset) {}
+
+    /// Use this as a hook to set the transform of the material's tiling grid, see
+    /// [`BackgroundUvTransform`]. Does nothing by default.
+    fn set_uv_transform(&mut self, _uv_transform: Affine2) {}
+
+    /// Use this as a hook to set how the material's texture repeats on each axis, see
+    /// [`BackgroundAddressMode`]. Does nothing by default.
+    fn set_address_mode(&mut self, _address_mode: BackgroundAddressMode) {}
+
+    /// Use this as a hook to limit the material to a single strip of tiles, see
+    /// [`BackgroundStrip`]. Does nothing by default.
+    fn set_strip(&mut self, _strip: BackgroundStrip) {}
+
+    /// Use this as a hook to snap the material to whole texels or a virtual pixel grid, see
+    /// [`BackgroundPixelSnap`]. Does nothing by default.
+    fn set_pixel_snap(&mut self, _pixel_snap: BackgroundPixelSnap) {}
+
+    /// Use this as a hook to tile only part of the material's texture, given in uvs from `0.0` to
+    /// `1.0`. Set by [`BackgroundFlipbook`] to the current frame. Does nothing by default.
+    fn set_frame_rect(&mut self, _frame_rect: Rect) {}
+
+    /// The textures the material tiles. The plugin sets their samplers to repeat when a
+    /// background is spawned or the material changes, so they don't need
+    /// [`SetImageRepeatingExt::set_image_repeating`]. Returns no textures by default.
+    fn textures(&self) -> Vec<Handle<Image>> {
+        Vec::new()
+    }
+
+    /// Use this as a hook to draw `fallback` instead of the material's
+    /// [`textures`](Self::textures) while they are loading or after they failed to load. `None`
+    /// draws the material's own textures again. Does nothing by default.
+    fn set_fallback(&mut self, _fallback: Option<Handle<Image>>) {}
+
+    /// Use this as a hook to tint the material, see [`BackgroundTint`]. Does nothing by default.
+    fn set_tint(&mut self, _tint: LinearRgba) {}
 }
 
-#[derive(AsBindGroup, Debug, Clone, Asset, TypePath, Default)]
+#[derive(AsBindGroup, Debug, Clone, Asset, Reflect)]
+#[reflect(Default, Debug)]
+#[bind_group_data(BackgroundMaterialKey)]
 pub struct BackgroundMaterial {
+    // The uniform fields are laid out to add up to a multiple of 16 bytes, which webgl2 requires.
+    /// Multiplied with the texture's color, including its alpha. Shared by every background using
+    /// the material, use [`BackgroundTint`] to tint a single one.
+    #[uniform(0)]
+    pub tint: LinearRgba,
+    /// Shown outside the texture on axes using [`AxisAddressMode::ClampToBorder`].
+    #[uniform(0)]
+    pub border_color: LinearRgba,
+    /// Fill below or left of the strip, see [`BackgroundStrip::fill_before`].
     #[uniform(0)]
-    pub movement_scale: f32,
-    /// webgl2 requires 16 byte alignment
+    pub strip_fill_before: LinearRgba,
+    /// Fill above or right of the strip, see [`BackgroundStrip::fill_after`].
     #[uniform(0)]
-    pub _wasm_padding: Vec3,
-    /// This image must have its [`SamplerDescriptor`] address_mode_* fields set to
-    /// [`AddressMode::Repeat`].
+    pub strip_fill_after: LinearRgba,
+    /// How much each tile is scaled, see [`BackgroundTileScale`].
+    #[uniform(0)]
+    pub tile_scale: Vec2,
+    /// First column of the tiling grid's transform, see [`BackgroundUvTransform`].
+    #[uniform(0)]
+    pub uv_x_axis: Vec2,
+    /// Second column of the tiling grid's transform, see [`BackgroundUvTransform`].
+    #[uniform(0)]
+    pub uv_y_axis: Vec2,
+    /// Translation of the tiling grid's transform, see [`BackgroundUvTransform`].
+    #[uniform(0)]
+    pub uv_translation: Vec2,
+    /// Top left corner of the part of the texture that is tiled, in uvs. See
+    /// [`ScrollingBackground::set_frame_rect`].
+    #[uniform(0)]
+    pub frame_min: Vec2,
+    /// Size of the part of the texture that is tiled, in uvs.
+    #[uniform(0)]
+    pub frame_size: Vec2,
+    /// The shader values of the background's [`BackgroundAddressMode`] for each axis.
+    #[uniform(0)]
+    pub address_mode: UVec2,
+    /// Fades the whole background, `0.0` is fully transparent and `1.0` leaves it unchanged.
+    #[uniform(0)]
+    pub opacity: f32,
+    /// The shader value of the background's [`BackgroundScaling`].
+    #[uniform(0)]
+    pub scaling: u32,
+    /// The shader value of the background's [`StripAxis`], `0` when it isn't a strip.
+    #[uniform(0)]
+    pub strip_axis: u32,
+    /// See [`BackgroundStrip::position`].
+    #[uniform(0)]
+    pub strip_position: f32,
+    /// The shader value of the background's [`BackgroundPixelSnap`].
+    #[uniform(0)]
+    pub pixel_snap: u32,
+    /// Size of the virtual pixel grid in world units, see [`BackgroundPixelSnap::Grid`].
+    #[uniform(0)]
+    pub pixel_grid: f32,
+    /// The tiled image. Its sampler is set to repeat once the background is spawned, see
+    /// [`ScrollingBackground::textures`].
+    pub texture: Handle<Image>,
+    /// The image that is drawn, either [`texture`](Self::texture) or the
+    /// [`BackgroundFallbackTexture`] while it's loading. Set by the plugin.
     #[texture(1)]
     #[sampler(2)]
-    pub texture: Handle<Image>,
+    pub displayed_texture: Handle<Image>,
+    /// How the background is blended with what is behind it.
+    pub blend_mode: BackgroundBlendMode,
+}
+
+impl Default for BackgroundMaterial {
+    fn default() -> Self {
+        Self {
+            tint: LinearRgba::WHITE,
+            border_color: LinearRgba::NONE,
+            strip_fill_before: LinearRgba::NONE,
+            strip_fill_after: LinearRgba::NONE,
+            tile_scale: Vec2::ONE,
+            uv_x_axis: Vec2::X,
+            uv_y_axis: Vec2::Y,
+            uv_translation: Vec2::ZERO,
+            frame_min: Vec2::ZERO,
+            frame_size: Vec2::ONE,
+            address_mode: UVec2::ZERO,
+            opacity: 1.0,
+            scaling: BackgroundScaling::default().shader_value(),
+            strip_axis: 0,
+            strip_position: 0.0,
+            pixel_snap: 0,
+            pixel_grid: 1.0,
+            texture: Handle::default(),
+            displayed_texture: TRANSPARENT_IMAGE_HANDLE,
+            blend_mode: BackgroundBlendMode::default(),
+        }
+    }
 }
 
 impl Material2d for BackgroundMaterial {
     fn vertex_shader() -> ShaderRef {
-        FULLSCREEN_SHADER_HANDLE.into()
+        TILED_BG_SHADER_HANDLE.into()
     }
     fn fragment_shader() -> ShaderRef {
         TILED_BG_SHADER_HANDLE.into()
     }
 
     fn alpha_mode(&self) -> AlphaMode2d {
-        AlphaMode2d::Blend
+        self.blend_mode.alpha_mode()
     }
 
     fn specialize(
         descriptor: &mut RenderPipelineDescriptor,
         _: &MeshVertexBufferLayoutRef,
-        _: Material2dKey<Self>,
+        key: Material2dKey<Self>,
     ) -> Result<(), SpecializedMeshPipelineError> {
-        descriptor.primitive = PrimitiveState::default();
-        descriptor.vertex.entry_point = "fullscreen_vertex_shader".into();
+        key.bind_group_data
+            .blend_mode
+            .specialize_pipeline(descriptor);
         Ok(())
     }
 }
 
-impl ScrollingBackground for BackgroundMaterial {
-    fn set_movement(&mut self, movement: f32) {
-        self.movement_scale = movement;
-    }
-}
-
-impl ScrollingBackground for &mut BackgroundMaterial {
-    fn set_movement(&mut self, movement: f32) {
-        self.movement_scale = movement;
-    }
+/// The pipeline key of [`BackgroundMaterial`] and [`LayeredBackgroundMaterial`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BackgroundMaterialKey {
+    pub blend_mode: BackgroundBlendMode,
 }
-/// A queue of images that need their sampler updated when they are loaded.
-#[derive(Resource, Default)]
-struct UpdateSamplerRepeating(Vec<Handle<Image>>);
 
-///Polls the update_sampler resource and swaps the asset's sampler out for a repeating sampler
-fn update_sampler_on_loaded_system(
-    asset_server: Res<AssetServer>,
-    mut update_sampler: ResMut<UpdateSamplerRepeating>,
-    mut images: ResMut<Assets<Image>>,
-) {
-    // Iterating over them backwards so removing one doesn't offset the index of the rest
-    let handles = update_sampler
-        .0
-        .iter()
-        .cloned()
-        .enumerate()
-        .rev()
-        .collect::<Vec<_>>();
-    for (index, handle) in handles {
-        match asset_server.get_load_state(&handle) {
-            Some(LoadState::Failed(_)) => {
-                // Failed to load, don't need to keep checking it
-                update_sampler.0.remove(index);
-            }
-            Some(LoadState::Loaded) => {
-                let bg_texture = images
-                    .get_mut(&handle)
-                    .expect("the image should be loaded at this point");
-
-                // If it already has a custom descriptor, update it otherwise create our own.
-                if let ImageSampler::Descriptor(descriptor) = &mut bg_texture.sampler {
-                    descriptor.address_mode_u = ImageAddressMode::Repeat;
-                    descriptor.address_mode_v = ImageAddressMode::Repeat;
-                    descriptor.address_mode_w = ImageAddressMode::Repeat;
-                } else {
-                    bg_texture.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
-                        address_mode_u: ImageAddressMode::Repeat,
-                        address_mode_v: ImageAddressMode::Repeat,
-                        address_mode_w: ImageAddressMode::Repeat,
-                        ..default()
-                    });
-                }
-                update_sampler.0.remove(index);
-                debug!("Updated image sampler to be repeating");
-            }
-            _ => {
-                // NotLoaded/Loading: not fully ready yet
-            }
+impl From<&BackgroundMaterial> for BackgroundMaterialKey {
+    fn from(material: &BackgroundMaterial) -> Self {
+        Self {
+            blend_mode: material.blend_mode,
         }
     }
 }
 
-#[derive(Component, Reflect)]
-#[reflect(Component, Default)]
-pub struct BackgroundMovementScale {
-    /// Determines how fast the background will scroll when the camera moves.
-    ///
-    /// # Examples
-    ///
-    /// - A scale of 0.0 the background will move with the camera.
-    /// - A scale of 1.0 the background will move opposite the camera at the same speed as the camera,
-    ///   making it stationary in the world.
-    /// - A scale of 2.0 the background will move twice as fast as the camera.
-    pub scale: f32,
-}
+impl ScrollingBackground for BackgroundMaterial {
+    // Movement, offset, scroll and tint are read per entity.
+    const PER_ENTITY_PARAMETERS: bool = true;
 
-impl Default for BackgroundMovementScale {
-    fn default() -> Self {
-        Self { scale: 1.0 }
+    fn set_movement(&mut self, _movement: f32) {}
+
+    fn set_scaling(&mut self, scaling: BackgroundScaling) {
+        self.scaling = scaling.shader_value();
     }
-}
 
-#[derive(Bundle)]
-pub struct CustomBackgroundImageBundle<T: Material2d> {
-    pub material: MeshMaterial2d<T>,
-    pub mesh: Mesh2d,
-    pub transform: Transform,
-    pub global_transform: GlobalTransform,
-    pub visibility: Visibility,
-    pub view_visibility: ViewVisibility,
-    pub inherited_visibility: InheritedVisibility,
-    pub movement_scale: BackgroundMovementScale,
-    pub no_frustum_culling: NoFrustumCulling,
-}
+    fn set_tile_scale(&mut self, tile_scale: Vec2) {
+        self.tile_scale = tile_scale;
+    }
 
-impl<T: Material2d + ScrollingBackground> CustomBackgroundImageBundle<T> {
-    pub fn with_material(material: T, materials: &mut Assets<T>) -> Self {
-        Self {
-            material: materials.add(material).into(),
-            mesh: BG_MESH_HANDLE.into(),
-            transform: Default::default(),
-            global_transform: Default::default(),
-            visibility: Default::default(),
-            view_visibility: Default::default(),
-            inherited_visibility: Default::default(),
-            movement_scale: Default::default(),
-            no_frustum_culling: Default::default(),
-        }
-    }
-}
-
-#[derive(Bundle)]
-pub struct BackgroundImageBundle {
-    pub material: MeshMaterial2d<BackgroundMaterial>,
-    pub mesh: Mesh2d,
-    pub transform: Transform,
-    pub global_transform: GlobalTransform,
-    pub visibility: Visibility,
-    pub view_visibility: ViewVisibility,
-    pub inherited_visibility: InheritedVisibility,
-    pub movement_scale: BackgroundMovementScale,
-    pub no_frustum_culling: NoFrustumCulling,
-}
-
-impl BackgroundImageBundle {
-    pub fn from_image(
-        image: Handle<Image>,
-        background_materials: &mut Assets<BackgroundMaterial>,
-    ) -> Self {
-        Self {
-            material: background_materials
-                .add(BackgroundMaterial {
-                    texture: image,
-                    movement_scale: 1.0,
-                    _wasm_padding: Vec3::ZERO,
-                })
-                .into(),
-            mesh: BG_MESH_HANDLE.into(),
-            transform: Default::default(),
-            global_transform: Default::default(),
-            visibility: Default::default(),
-            view_visibility: Default::default(),
-            inherited_visibility: Default::default(),
-            movement_scale: Default::default(),
-            no_frustum_culling: Default::default(),
-        }
-    }
-
-    pub fn with_movement_scale(mut self, scale: f32) -> Self {
-        self.movement_scale.scale = scale;
-        self
+    fn set_uv_transform(&mut self, uv_transform: Affine2) {
+        self.uv_x_axis = uv_transform.matrix2.x_axis;
+        self.uv_y_axis = uv_transform.matrix2.y_axis;
+        self.uv_translation = uv_transform.translation;
     }
 
-    pub fn at_z_layer(mut self, z: f32) -> Self {
-        self.transform.translation.z = z;
+    fn set_address_mode(&mut self, address_mode: BackgroundAddressMode) {
+        self.address_mode = address_mode.shader_value();
+        self.border_color = address_mode.border_color;
+    }
+
+    fn set_strip(&mut self, strip: BackgroundStrip) {
+        self.strip_axis = strip.axis.shader_value();
+        self.strip_position = strip.position;
+        self.strip_fill_before = strip.fill_before;
+        self.strip_fill_after = strip.fill_after;
+    }
+
+    fn set_pixel_snap(&mut self, pixel_snap: BackgroundPixelSnap) {
+        self.pixel_snap = pixel_snap.shader_value();
+        if let BackgroundPixelSnap::Grid(size) = pixel_snap {
+            self.pixel_grid = size;
+        }
+    }
+
+    fn set_frame_rect(&mut self, frame_rect: Rect) {
+        self.frame_min = frame_rect.min;
+        self.frame_size = frame_rect.size();
+    }
+
+    fn textures(&self) -> Vec<Handle<Image>> {
+        vec![self.texture.clone()]
+    }
+
+    fn set_fallback(&mut self, fallback: Option<Handle<Image>>) {
+        self.displayed_texture = fallback.unwrap_or_else(|| self.texture.clone());
+    }
+}
+
+impl ScrollingBackground for &mut BackgroundMaterial {
+    const PER_ENTITY_PARAMETERS: bool = BackgroundMaterial::PER_ENTITY_PARAMETERS;
+
+    fn set_movement(&mut self, movement: f32) {
+        (**self).set_movement(movement);
+    }
+
+    fn set_movement_axes(&mut self, movement: Vec2) {
+        (**self).set_movement_axes(movement);
+    }
+
+    fn set_scaling(&mut self, scaling: BackgroundScaling) {
+        (**self).set_scaling(scaling);
+    }
+
+    fn set_scroll(&mut self, scroll: Vec2) {
+        (**self).set_scroll(scroll);
+    }
+
+    fn set_tile_scale(&mut self, tile_scale: Vec2) {
+        (**self).set_tile_scale(tile_scale);
+    }
+
+    fn set_offset(&mut self, offset: BackgroundOffset) {
+        (**self).set_offset(offset);
+    }
+
+    fn set_uv_transform(&mut self, uv_transform: Affine2) {
+        (**self).set_uv_transform(uv_transform);
+    }
+
+    fn set_address_mode(&mut self, address_mode: BackgroundAddressMode) {
+        (**self).set_address_mode(address_mode);
+    }
+
+    fn set_strip(&mut self, strip: BackgroundStrip) {
+        (**self).set_strip(strip);
+    }
+
+    fn set_pixel_snap(&mut self, pixel_snap: BackgroundPixelSnap) {
+        (**self).set_pixel_snap(pixel_snap);
+    }
+
+    fn set_frame_rect(&mut self, frame_rect: Rect) {
+        (**self).set_frame_rect(frame_rect);
+    }
+
+    fn textures(&self) -> Vec<Handle<Image>> {
+        (**self).textures()
+    }
+
+    fn set_fallback(&mut self, fallback: Option<Handle<Image>>) {
+        (**self).set_fallback(fallback);
+    }
+
+    fn set_tint(&mut self, tint: LinearRgba) {
+        (**self).set_tint(tint);
+    }
+}
+
+/// Sets of the systems [`TilingBackgroundPlugin`]s share, to order your own systems against.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TilingBackgroundSystems {
+    /// Sets the samplers of textures that should repeat once they are loaded.
+    UpdateSamplers,
+}
+
+/// Sets the samplers of [`RepeatingImages`], added once no matter how many
+/// [`TilingBackgroundPlugin`]s there are.
+struct RepeatingImagesPlugin;
+
+impl Plugin for RepeatingImagesPlugin {
+    fn build(&self, app: &mut App) {
+        // Images without a sampler of their own keep the app's default filtering when they're made
+        // to repeat.
+        let default_sampler = app
+            .get_added_plugins::<ImagePlugin>()
+            .first()
+            .map_or_else(ImageSamplerDescriptor::default, |plugin| {
+                plugin.default_sampler.clone()
+            });
+        app.insert_resource(RepeatingImages {
+            default_sampler,
+            ..default()
+        })
+        .add_systems(
+            Update,
+            update_sampler_on_loaded_system.in_set(TilingBackgroundSystems::UpdateSamplers),
+        );
+    }
+}
+
+/// The images whose sampler should repeat, along with the address modes to use. Kept after the
+/// sampler is set, so it can be set again when the image is reloaded.
+#[derive(Resource, Default)]
+struct RepeatingImages {
+    address_modes: HashMap<AssetId<Image>, BackgroundAddressMode>,
+    /// Images added since the last update, they may have been loaded before they were added.
+    queued: Vec<AssetId<Image>>,
+    /// The sampler of images without one of their own, the [`ImagePlugin`]'s default sampler.
+    default_sampler: ImageSamplerDescriptor,
+}
+
+impl RepeatingImages {
+    fn insert(&mut self, image: AssetId<Image>, address_mode: BackgroundAddressMode) {
+        self.address_modes.insert(image, address_mode);
+        self.queued.push(image);
+    }
+}
+
+/// Sets the sampler of [`RepeatingImages`] when they are queued, loaded, added at runtime or
+/// modified, e.g. by hot reloading. Does nothing while no images change.
+fn update_sampler_on_loaded_system(
+    mut image_events: EventReader<AssetEvent<Image>>,
+    mut repeating_images: ResMut<RepeatingImages>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    if image_events.is_empty() && repeating_images.queued.is_empty() {
+        return;
+    }
+    let repeating_images = &mut *repeating_images;
+    let mut changed = std::mem::take(&mut repeating_images.queued);
+    for event in image_events.read() {
+        match event {
+            AssetEvent::Added { id }
+            | AssetEvent::Modified { id }
+            | AssetEvent::LoadedWithDependencies { id } => {
+                if repeating_images.address_modes.contains_key(id) {
+                    changed.push(*id);
+                }
+            }
+            AssetEvent::Removed { id } | AssetEvent::Unused { id } => {
+                repeating_images.address_modes.remove(id);
+            }
+        }
+    }
+
+    for id in changed {
+        let Some(&address_mode) = repeating_images.address_modes.get(&id) else {
+            continue;
+        };
+        // Images that are still loading are set once their load event arrives. Images loaded with
+        // `load_repeating` or a `.meta` file may already be set up, leave those alone so they
+        // aren't uploaded to the GPU again. This also ignores the `Modified` event caused by
+        // setting the sampler.
+        let needs_update = images
+            .get(id)
+            .is_some_and(|image| !sampler_has_address_mode(&image.sampler, address_mode));
+        if !needs_update {
+            continue;
+        }
+        if let Some(image) = images.get_mut(id) {
+            set_sampler_address_mode(
+                &mut image.sampler,
+                address_mode,
+                &repeating_images.default_sampler,
+            );
+            debug!(
+                "Updated image sampler address modes to {:?}, {:?}",
+                address_mode.x, address_mode.y
+            );
+        }
+    }
+}
+
+/// Sets the address modes of a sampler, keeping the rest of its descriptor if it has one. Samplers
+/// without a descriptor start from `base`.
+fn set_sampler_address_mode(
+    sampler: &mut ImageSampler,
+    address_mode: BackgroundAddressMode,
+    base: &ImageSamplerDescriptor,
+) {
+    let address_mode_u = address_mode.x.image_address_mode();
+    let address_mode_v = address_mode.y.image_address_mode();
+    // If it already has a custom descriptor, update it otherwise create our own.
+    if let ImageSampler::Descriptor(descriptor) = sampler {
+        descriptor.address_mode_u = address_mode_u;
+        descriptor.address_mode_v = address_mode_v;
+        descriptor.address_mode_w = ImageAddressMode::Repeat;
+    } else {
+        *sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+            address_mode_u,
+            address_mode_v,
+            address_mode_w: ImageAddressMode::Repeat,
+            ..base.clone()
+        });
+    }
+}
+
+fn sampler_has_address_mode(sampler: &ImageSampler, address_mode: BackgroundAddressMode) -> bool {
+    match sampler {
+        // `ImageAddressMode` can't be compared, the wgpu type it mirrors can.
+        ImageSampler::Descriptor(descriptor) => {
+            AddressMode::from(descriptor.address_mode_u)
+                == AddressMode::from(address_mode.x.image_address_mode())
+                && AddressMode::from(descriptor.address_mode_v)
+                    == AddressMode::from(address_mode.y.image_address_mode())
+        }
+        ImageSampler::Default => false,
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct BackgroundMovementScale {
+    /// Determines how fast the background will scroll when the camera moves.
+    ///
+    /// # Examples
+    ///
+    /// - A scale of 0.0 the background will move with the camera.
+    /// - A scale of 1.0 the background will move opposite the camera at the same speed as the camera,
+    ///   making it stationary in the world.
+    /// - A scale of 2.0 the background will move twice as fast as the camera.
+    ///
+    /// Add [`BackgroundMovementAxes`] to scale each axis separately.
+    pub scale: f32,
+}
+
+impl BackgroundMovementScale {
+    pub const fn new(scale: f32) -> Self {
+        Self { scale }
+    }
+}
+
+impl Default for BackgroundMovementScale {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl From<f32> for BackgroundMovementScale {
+    fn from(scale: f32) -> Self {
+        Self::new(scale)
+    }
+}
+
+/// Multiplies a background's [`BackgroundMovementScale`] separately on each axis.
+///
+/// A factor of `(1.0, 0.0)` scrolls the background horizontally at its movement scale but keeps it
+/// locked to the screen vertically.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component, Default)]
+pub struct BackgroundMovementAxes(pub Vec2);
+
+impl Default for BackgroundMovementAxes {
+    fn default() -> Self {
+        Self(Vec2::ONE)
+    }
+}
+
+/// The movement scale of each axis, combining [`BackgroundMovementScale`] and
+/// [`BackgroundMovementAxes`].
+fn movement_scale(
+    scale: Option<&BackgroundMovementScale>,
+    axes: Option<&BackgroundMovementAxes>,
+) -> Vec2 {
+    scale.map_or(1.0, |scale| scale.scale) * axes.map_or(Vec2::ONE, |axes| axes.0)
+}
+
+/// Determines how a background's texels map onto the screen.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Component, Default)]
+pub enum BackgroundScaling {
+    /// One texel covers one physical pixel, tiles keep their size on screen however the camera
+    /// projection is zoomed.
+    #[default]
+    Screen,
+    /// One texel covers one world unit, the same as a [`Sprite`]. Tiles zoom along with the camera
+    /// projection, whatever its [`ScalingMode`](bevy::render::camera::ScalingMode) is.
+    World,
+}
+
+impl BackgroundScaling {
+    /// The value `braymatter::bglib` expects for this mode, `SCALING_SCREEN` or `SCALING_WORLD`.
+    pub fn shader_value(self) -> u32 {
+        match self {
+            BackgroundScaling::Screen => 0,
+            BackgroundScaling::World => 1,
+        }
+    }
+}
+
+/// How a background is blended with what is drawn behind it, e.g. other backgrounds.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[reflect(Default)]
+pub enum BackgroundBlendMode {
+    /// Covers everything behind the background, ignoring the texture's alpha. Skips blending, so
+    /// it's the cheapest mode for the bottom layer.
+    ///
+    /// Without a [`BackgroundPass`] these backgrounds are drawn in Bevy's opaque phase, which isn't
+    /// sorted by z: before every transparent background, and in no particular order among each
+    /// other. Only use it for a single bottom layer then, or add a [`BackgroundPass`] to the camera
+    /// to draw every background sorted by z.
+    Opaque,
+    /// Regular alpha blending.
+    #[default]
+    Blend,
+    /// Alpha blending for textures whose colors are already multiplied by their alpha.
+    Premultiplied,
+    /// Adds the background's color to what is behind it, e.g. for light rays.
+    Add,
+    /// Multiplies what is behind the background with its color, darkening it, e.g. for shadows.
+    Multiply,
+    /// The inverse of [`Multiply`](Self::Multiply), brightening what is behind the background,
+    /// e.g. for fog.
+    Screen,
+}
+
+impl BackgroundBlendMode {
+    /// The alpha mode of materials using this mode, only [`Opaque`](Self::Opaque) is drawn in
+    /// Bevy's opaque phase.
+    pub fn alpha_mode(self) -> AlphaMode2d {
+        match self {
+            BackgroundBlendMode::Opaque => AlphaMode2d::Opaque,
+            _ => AlphaMode2d::Blend,
+        }
+    }
+
+    /// Sets the blend state and the shader defs `braymatter::bglib::blend_output` uses for this
+    /// mode. Call it from [`Material2d::specialize`] of custom materials, along with returning
+    /// [`alpha_mode`](Self::alpha_mode) from [`Material2d::alpha_mode`].
+    pub fn specialize_pipeline(self, descriptor: &mut RenderPipelineDescriptor) {
+        let Some(fragment) = descriptor.fragment.as_mut() else {
+            return;
+        };
+        // Keeps the alpha of what is behind the background.
+        const KEEP_ALPHA: BlendComponent = BlendComponent {
+            src_factor: BlendFactor::Zero,
+            dst_factor: BlendFactor::One,
+            operation: BlendOperation::Add,
+        };
+        let blend = match self {
+            // Set by the 2d mesh pipeline for the alpha mode.
+            BackgroundBlendMode::Opaque | BackgroundBlendMode::Blend => None,
+            BackgroundBlendMode::Premultiplied => Some(BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+            BackgroundBlendMode::Add => Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+                alpha: KEEP_ALPHA,
+            }),
+            BackgroundBlendMode::Multiply => Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::Dst,
+                    dst_factor: BlendFactor::OneMinusSrcAlpha,
+                    operation: BlendOperation::Add,
+                },
+                alpha: KEEP_ALPHA,
+            }),
+            BackgroundBlendMode::Screen => Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::OneMinusSrc,
+                    operation: BlendOperation::Add,
+                },
+                alpha: KEEP_ALPHA,
+            }),
+        };
+        if let Some(blend) = blend {
+            for target in fragment.targets.iter_mut().flatten() {
+                target.blend = Some(blend);
+            }
+        }
+
+        match self {
+            BackgroundBlendMode::Opaque => fragment.shader_defs.push("BACKGROUND_OPAQUE".into()),
+            BackgroundBlendMode::Blend => {}
+            BackgroundBlendMode::Premultiplied => {
+                fragment
+                    .shader_defs
+                    .push("BACKGROUND_PREMULTIPLIED_TEXTURE".into());
+                fragment
+                    .shader_defs
+                    .push("BACKGROUND_PREMULTIPLIED_OUTPUT".into());
+            }
+            BackgroundBlendMode::Add
+            | BackgroundBlendMode::Multiply
+            | BackgroundBlendMode::Screen => {
+                fragment
+                    .shader_defs
+                    .push("BACKGROUND_PREMULTIPLIED_OUTPUT".into());
+            }
+        }
+    }
+}
+
+/// Snaps a background to whole pixels, so pixel art scrolls crisply at any
+/// [`BackgroundMovementScale`] instead of shimmering between texels.
+///
+/// Snapping also samples the texture as if its sampler used nearest filtering, so it works with
+/// linear samplers too. Backgrounds without this component use the [`DefaultBackgroundPixelSnap`].
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[reflect(Component, Default)]
+pub enum BackgroundPixelSnap {
+    /// Scrolls smoothly.
+    #[default]
+    Off,
+    /// Moves the background in steps of whole texels of its texture, after
+    /// [`BackgroundTileScale`] and [`BackgroundScaling`] are applied.
+    Texel,
+    /// Moves the background in steps of a virtual pixel grid, given as the size of a pixel in world
+    /// units. Use this to match a low resolution camera setup. Sizes that aren't positive are
+    /// ignored with a warning.
+    Grid(f32),
+}
+
+impl BackgroundPixelSnap {
+    /// The value `braymatter::bglib` expects for this mode, `SNAP_OFF`, `SNAP_TEXEL` or
+    /// `SNAP_GRID`.
+    pub fn shader_value(self) -> u32 {
+        match self {
+            BackgroundPixelSnap::Off => 0,
+            BackgroundPixelSnap::Texel => 1,
+            BackgroundPixelSnap::Grid(_) => 2,
+        }
+    }
+}
+
+/// The [`BackgroundPixelSnap`] of backgrounds that don't have their own, e.g. to snap every
+/// background in a pixel art game.
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct DefaultBackgroundPixelSnap(pub BackgroundPixelSnap);
+
+/// Binds a background to a single camera.
+///
+/// The background is only drawn by that camera. If the camera is despawned the background isn't
+/// drawn at all.
+///
+/// To share a background between a few cameras, use [`RenderLayers`](bevy::render::view::RenderLayers)
+/// instead, backgrounds follow the same layer rules as any other [`Mesh2d`].
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct BackgroundCamera(pub Entity);
+
+/// Scrolls a background on its own, in world units per second, on top of any movement caused by the
+/// camera. Useful for drifting clouds, starfields or title screens.
+///
+/// Driven by [`Time`], so pausing or scaling virtual time affects it too.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[reflect(Component, Default)]
+#[require(BackgroundScrollPosition)]
+pub struct BackgroundScrollVelocity(pub Vec2);
+
+/// How far a background has scrolled on its own, in world units.
+///
+/// Advanced every frame by [`BackgroundScrollVelocity`], it can also be set directly.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[reflect(Component, Default)]
+pub struct BackgroundScrollPosition(pub Vec2);
+
+/// Where the frames of a [`BackgroundFlipbook`] are in its texture.
+#[derive(Reflect, Clone, Debug, PartialEq)]
+pub enum FlipbookFrames {
+    /// The textures of an atlas layout, in order. The layout has to be made for the background's
+    /// texture.
+    Atlas(Handle<TextureAtlasLayout>),
+    /// A grid of equally sized frames covering the whole texture, read left to right and top to
+    /// bottom. `count` can be less than `columns * rows` when the last row isn't full.
+    Grid { columns: u32, rows: u32, count: u32 },
+}
+
+/// Animates a background by tiling one frame of a sprite sheet at a time, e.g. for water, lava or
+/// space dust.
+///
+/// Only the current frame repeats, neighboring frames don't bleed in at the seams. Frames advance
+/// using [`Time`], so pausing or scaling virtual time affects the animation too. Address modes,
+/// strips and snapping apply to the frame as if it was the whole texture.
+///
+/// The current frame is stored in the background's material, so a background with a flipbook gets
+/// its own copy of a material that other backgrounds share.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct BackgroundFlipbook {
+    pub frames: FlipbookFrames,
+    /// Index of the frame that is shown.
+    pub frame: usize,
+    /// Time each frame is shown, should be repeating.
+    pub timer: Timer,
+}
+
+impl BackgroundFlipbook {
+    /// Plays the textures of an atlas layout at `fps` frames per second. An `fps` of `0.0` or less
+    /// shows the first frame without playing.
+    pub fn from_atlas(layout: Handle<TextureAtlasLayout>, fps: f32) -> Self {
+        Self {
+            frames: FlipbookFrames::Atlas(layout),
+            frame: 0,
+            timer: frame_timer(fps),
+        }
+    }
+
+    /// Plays every cell of a `columns` by `rows` grid at `fps` frames per second. An `fps` of `0.0`
+    /// or less shows the first frame without playing.
+    pub fn from_grid(columns: u32, rows: u32, fps: f32) -> Self {
+        Self {
+            frames: FlipbookFrames::Grid {
+                columns,
+                rows,
+                count: columns * rows,
+            },
+            frame: 0,
+            timer: frame_timer(fps),
+        }
+    }
+
+    /// Only plays the first `count` cells of a grid, does nothing for atlases.
+    pub fn with_frame_count(mut self, count: u32) -> Self {
+        if let FlipbookFrames::Grid { count: frames, .. } = &mut self.frames {
+            *frames = count;
+        }
         self
     }
+
+    /// How many frames the flipbook has, or `None` if the atlas layout isn't loaded.
+    pub fn frame_count(&self, layouts: &Assets<TextureAtlasLayout>) -> Option<usize> {
+        match &self.frames {
+            FlipbookFrames::Atlas(layout) => layouts.get(layout).map(|layout| layout.len()),
+            FlipbookFrames::Grid { count, .. } => Some(*count as usize),
+        }
+    }
+
+    /// The current frame in uvs, or `None` if the atlas layout isn't loaded or the frame doesn't
+    /// exist.
+    pub fn frame_rect(&self, layouts: &Assets<TextureAtlasLayout>) -> Option<Rect> {
+        match &self.frames {
+            FlipbookFrames::Atlas(layout) => {
+                let layout = layouts.get(layout)?;
+                let rect = layout.textures.get(self.frame)?.as_rect();
+                let size = layout.size.as_vec2();
+                Some(Rect::from_corners(rect.min / size, rect.max / size))
+            }
+            FlipbookFrames::Grid {
+                columns,
+                rows,
+                count,
+            } => {
+                let frame = self.frame as u32;
+                if frame >= *count || *columns == 0 || *rows == 0 {
+                    return None;
+                }
+                let size = Vec2::ONE / UVec2::new(*columns, *rows).as_vec2();
+                let min = UVec2::new(frame % columns, frame / columns).as_vec2() * size;
+                Some(Rect::from_corners(min, min + size))
+            }
+        }
+    }
+}
+
+/// A repeating timer that finishes `fps` times per second, or a paused one if `fps` isn't positive
+/// or is too small or large to be a [`Duration`].
+fn frame_timer(fps: f32) -> Timer {
+    match Duration::try_from_secs_f32(fps.recip()) {
+        Ok(frame_time) if !frame_time.is_zero() => Timer::new(frame_time, TimerMode::Repeating),
+        _ => {
+            let mut timer = Timer::new(Duration::MAX, TimerMode::Repeating);
+            timer.pause();
+            timer
+        }
+    }
+}
+
+/// Scales the tiles of a background, a scale of `4.0` shows a 64x64 texture as 256x256 tiles and
+/// `0.5` shrinks it down to 32x32.
+///
+/// The unscaled tile size depends on the background's [`BackgroundScaling`].
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component, Default)]
+pub struct BackgroundTileScale(pub Vec2);
+
+impl BackgroundTileScale {
+    /// Uses the same scale on both axes.
+    pub const fn splat(scale: f32) -> Self {
+        Self(Vec2::splat(scale))
+    }
+}
+
+impl Default for BackgroundTileScale {
+    fn default() -> Self {
+        Self(Vec2::ONE)
+    }
+}
+
+/// Tints a single background, on top of its material's tint. Unlike the material's tint, each
+/// background sharing a material can have its own.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component, Default)]
+pub struct BackgroundTint(pub LinearRgba);
+
+impl Default for BackgroundTint {
+    fn default() -> Self {
+        Self(LinearRgba::WHITE)
+    }
+}
+
+/// Lines a background up with the world, similar to Godot's `motion_offset` and
+/// `scroll_base_offset`.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[reflect(Component, Default)]
+pub struct BackgroundOffset {
+    /// Moves the tiles by this many world units. With the camera at
+    /// [`parallax_origin`](Self::parallax_origin), the corner of a tile sits at this world position.
+    pub offset: Vec2,
+    /// The camera position at which parallax has no effect on the background. Layers with
+    /// different [`BackgroundMovementScale`]s line up when the camera is here, e.g. a horizon.
+    pub parallax_origin: Vec2,
+}
+
+/// Rotates, skews or scales the tiling grid of a background.
+///
+/// The transform is applied to the background's coordinates in world units, after parallax and
+/// [`BackgroundOffset`], so rotations stay square for non-square textures and the translation is in
+/// world units.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component, Default)]
+pub struct BackgroundUvTransform(pub Affine2);
+
+impl BackgroundUvTransform {
+    /// Rotates the tiling grid counterclockwise by `angle` radians.
+    pub fn from_rotation(angle: f32) -> Self {
+        // The transform maps screen positions onto the texture, so the grid turns the other way.
+        Self(Affine2::from_angle(-angle))
+    }
+
+    /// Skews the tiling grid by the given angles in radians, `x` leans the vertical lines of the grid
+    /// and `y` the horizontal ones.
+    pub fn from_skew(skew: Vec2) -> Self {
+        Self(Affine2::from_mat2(Mat2::from_cols(
+            Vec2::new(1.0, skew.y.tan()),
+            Vec2::new(skew.x.tan(), 1.0),
+        )))
+    }
+
+    /// Scales the coordinates of the tiling grid, a scale of `2.0` fits twice as many tiles.
+    pub fn from_scale(scale: Vec2) -> Self {
+        Self(Affine2::from_scale(scale))
+    }
+
+    /// Applies `other` after this transform.
+    pub fn then(self, other: Self) -> Self {
+        Self(other.0 * self.0)
+    }
+}
+
+impl Default for BackgroundUvTransform {
+    fn default() -> Self {
+        Self(Affine2::IDENTITY)
+    }
+}
+
+/// How a background's texture is repeated along one axis.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Default)]
+pub enum AxisAddressMode {
+    /// Repeats the texture.
+    #[default]
+    Repeat,
+    /// Repeats the texture, flipping every other tile.
+    MirrorRepeat,
+    /// Shows the texture once and stretches its edge texels outwards.
+    Clamp,
+    /// Shows the texture once and fills the rest with
+    /// [`BackgroundAddressMode::border_color`], transparent by default.
+    ClampToBorder,
+}
+
+impl AxisAddressMode {
+    /// The value `braymatter::bglib` expects for this mode, one of its `ADDRESS_*` constants.
+    pub fn shader_value(self) -> u32 {
+        match self {
+            AxisAddressMode::Repeat => 0,
+            AxisAddressMode::MirrorRepeat => 1,
+            AxisAddressMode::Clamp => 2,
+            AxisAddressMode::ClampToBorder => 3,
+        }
+    }
+
+    /// The closest sampler address mode. Borders are drawn by the shader, since clamping to a
+    /// border color isn't supported everywhere.
+    pub fn image_address_mode(self) -> ImageAddressMode {
+        match self {
+            AxisAddressMode::Repeat => ImageAddressMode::Repeat,
+            AxisAddressMode::MirrorRepeat => ImageAddressMode::MirrorRepeat,
+            AxisAddressMode::Clamp | AxisAddressMode::ClampToBorder => {
+                ImageAddressMode::ClampToEdge
+            }
+        }
+    }
+}
+
+/// Sets how a background's texture repeats on each axis, e.g. a horizon strip that repeats
+/// horizontally but not vertically.
+///
+/// This is applied in the shader, so backgrounds sharing an image can use different modes. The
+/// image's sampler only matters for filtering right at the texture's edges, see
+/// [`SetImageRepeatingExt::set_image_address_mode`].
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component, Default)]
+pub struct BackgroundAddressMode {
+    pub x: AxisAddressMode,
+    pub y: AxisAddressMode,
+    /// Color shown outside the texture on axes using [`AxisAddressMode::ClampToBorder`].
+    pub border_color: LinearRgba,
+}
+
+impl BackgroundAddressMode {
+    /// Uses the same mode on both axes.
+    pub fn new(mode: AxisAddressMode) -> Self {
+        Self {
+            x: mode,
+            y: mode,
+            ..default()
+        }
+    }
+
+    pub fn with_border_color(mut self, border_color: impl Into<LinearRgba>) -> Self {
+        self.border_color = border_color.into();
+        self
+    }
+
+    /// The shader values of both axes.
+    pub fn shader_value(&self) -> UVec2 {
+        UVec2::new(self.x.shader_value(), self.y.shader_value())
+    }
+}
+
+impl Default for BackgroundAddressMode {
+    fn default() -> Self {
+        Self {
+            x: AxisAddressMode::Repeat,
+            y: AxisAddressMode::Repeat,
+            border_color: LinearRgba::NONE,
+        }
+    }
+}
+
+/// The axis a [`BackgroundStrip`] repeats along.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Default)]
+pub enum StripAxis {
+    /// Repeats left to right, e.g. a mountain range or treeline.
+    #[default]
+    Horizontal,
+    /// Repeats bottom to top.
+    Vertical,
+}
+
+impl StripAxis {
+    /// The value `braymatter::bglib` expects for this axis, `STRIP_HORIZONTAL` or `STRIP_VERTICAL`.
+    pub fn shader_value(self) -> u32 {
+        match self {
+            StripAxis::Horizontal => 1,
+            StripAxis::Vertical => 2,
+        }
+    }
+}
+
+/// Limits a background to a single row (or column) of tiles anchored at a world coordinate, such as
+/// a mountain range that repeats horizontally at a fixed height.
+///
+/// The strip is positioned in the background's own space, so it moves with its
+/// [`BackgroundMovementScale`] and sits exactly at [`position`](Self::position) while the camera is
+/// at the [`BackgroundOffset::parallax_origin`].
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[reflect(Component, Default)]
+pub struct BackgroundStrip {
+    pub axis: StripAxis,
+    /// World coordinate of the strip's bottom edge for horizontal strips, or its left edge for
+    /// vertical ones.
+    pub position: f32,
+    /// Shown below a horizontal strip or left of a vertical one, transparent by default.
+    pub fill_before: LinearRgba,
+    /// Shown above a horizontal strip or right of a vertical one, transparent by default.
+    pub fill_after: LinearRgba,
+}
+
+impl BackgroundStrip {
+    /// A strip repeating horizontally with its bottom edge at world `y`.
+    pub fn horizontal(y: f32) -> Self {
+        Self {
+            axis: StripAxis::Horizontal,
+            position: y,
+            ..default()
+        }
+    }
+
+    /// A strip repeating vertically with its left edge at world `x`.
+    pub fn vertical(x: f32) -> Self {
+        Self {
+            axis: StripAxis::Vertical,
+            position: x,
+            ..default()
+        }
+    }
+
+    /// Fills the space outside the strip, `before` is below (or left of) the strip and `after` is
+    /// above (or right of) it.
+    pub fn with_fill(
+        mut self,
+        before: impl Into<LinearRgba>,
+        after: impl Into<LinearRgba>,
+    ) -> Self {
+        self.fill_before = before.into();
+        self.fill_after = after.into();
+        self
+    }
+}
+
+/// Marks an entity as a tiling background.
+///
+/// Requires everything a background needs to be drawn, so spawning it alongside a material is
+/// enough:
+///
+/// ```ignore
+/// commands.spawn((TilingBackground, MeshMaterial2d(materials.add(material))));
+/// ```
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+#[require(
+    Mesh2d(background_mesh),
+    Transform,
+    Visibility,
+    BackgroundMovementScale,
+    BackgroundScaling,
+    BackgroundLoadState,
+    NoFrustumCulling
+)]
+pub struct TilingBackground;
+
+/// Whether a background's textures are ready to be drawn, see [`backgrounds_ready`].
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Component, Default)]
+pub enum BackgroundLoadState {
+    /// The material or one of its textures is still loading, or its samplers aren't set yet.
+    #[default]
+    Loading,
+    /// Everything is loaded and the background tiles without seams.
+    Ready,
+    /// One of the textures failed to load, see [`BackgroundLoadFailed`]. The background loads again
+    /// once that texture is loaded after all.
+    Failed,
+}
+
+/// Sent once a background's textures are loaded and their samplers are set. Sent again if the
+/// background's textures change and finish loading.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BackgroundReady {
+    pub entity: Entity,
+}
+
+/// Sent when one of a background's textures fails to load.
+#[derive(Event, Clone, Debug)]
+pub struct BackgroundLoadFailed {
+    pub entity: Entity,
+    pub handle: Handle<Image>,
+    pub error: TilingBackgroundError,
+}
+
+/// Errors of the plugin, e.g. sent with [`BackgroundLoadFailed`].
+#[derive(Debug, Clone)]
+pub enum TilingBackgroundError {
+    /// A background's texture failed to load.
+    TextureLoadFailed(Arc<AssetLoadError>),
+    /// An image was queued for a repeating sampler in an app without a [`TilingBackgroundPlugin`].
+    PluginNotAdded,
+}
+
+impl fmt::Display for TilingBackgroundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TilingBackgroundError::TextureLoadFailed(error) => {
+                write!(f, "failed to load background texture: {error}")
+            }
+            TilingBackgroundError::PluginNotAdded => {
+                write!(
+                    f,
+                    "TilingBackgroundPlugin must be added to set image samplers"
+                )
+            }
+        }
+    }
+}
+
+impl Error for TilingBackgroundError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TilingBackgroundError::TextureLoadFailed(error) => Some(&**error),
+            TilingBackgroundError::PluginNotAdded => None,
+        }
+    }
+}
+
+/// The image drawn on backgrounds while their textures are loading or after they failed to load.
+/// Defaults to `None`, which draws nothing until the textures are loaded.
+#[derive(Resource, Reflect, Clone, Debug, Default, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct BackgroundFallbackTexture(pub Option<Handle<Image>>);
+
+impl BackgroundFallbackTexture {
+    /// Draws a grey checkerboard, which makes missing textures easy to spot.
+    pub fn checkerboard() -> Self {
+        Self(Some(CHECKERBOARD_TEXTURE_HANDLE))
+    }
+}
+
+/// A run condition that is true once every background is
+/// [`Ready`](BackgroundLoadState::Ready), e.g. to keep a loading screen up until every parallax
+/// layer can be drawn:
+///
+/// ```ignore
+/// app.add_systems(Update, close_loading_screen.run_if(backgrounds_ready));
+/// ```
+///
+/// It's false while no backgrounds are spawned, so it doesn't pass before the backgrounds of the
+/// next screen are. Backgrounds that failed to load keep it false, listen to
+/// [`BackgroundLoadFailed`] to handle them.
+pub fn backgrounds_ready(backgrounds: Query<&BackgroundLoadState>) -> bool {
+    !backgrounds.is_empty()
+        && backgrounds
+            .iter()
+            .all(|load_state| *load_state == BackgroundLoadState::Ready)
+}
+
+fn background_mesh() -> Mesh2d {
+    Mesh2d(BG_MESH_HANDLE)
+}
+
+/// A [`TilingBackground`] that also requires a [`MeshMaterial2d<T>`].
+///
+/// Useful when the material handle is inserted later, or to filter queries down to the
+/// backgrounds of one material type.
+#[derive(Component)]
+#[require(TilingBackground, MeshMaterial2d<T>)]
+pub struct TilingBackgroundMaterial<T: Material2d>(PhantomData<T>);
+
+impl<T: Material2d> Default for TilingBackgroundMaterial<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
 }
 
 struct SetImageRepeatingCommand {
     image: Handle<Image>,
+    address_mode: BackgroundAddressMode,
 }
 
 impl Command for SetImageRepeatingCommand {
     fn apply(self, world: &mut World) {
-        let mut samplers = world.resource_mut::<UpdateSamplerRepeating>();
-        samplers.0.push(self.image);
+        let Some(mut repeating_images) = world.get_resource_mut::<RepeatingImages>() else {
+            error!("{}", TilingBackgroundError::PluginNotAdded);
+            return;
+        };
+        repeating_images.insert(self.image.id(), self.address_mode);
     }
 }
 
 pub trait SetImageRepeatingExt {
     fn set_image_repeating(&mut self, image: Handle<Image>);
+
+    fn set_image_address_mode(&mut self, image: Handle<Image>, address_mode: BackgroundAddressMode);
 }
 
 impl SetImageRepeatingExt for Commands<'_, '_> {
     /// Queues this image to have it's [`SamplerDescriptor`] changed to be repeating once the
     /// image is loaded. This may take more than a frame to apply.
+    ///
+    /// Works for images added to [`Assets<Image>`] at runtime too, and is applied again whenever
+    /// the image is modified or hot reloaded. Images without a sampler of their own get the
+    /// [`ImagePlugin`]'s default sampler, if the plugin was added before [`TilingBackgroundPlugin`].
     fn set_image_repeating(&mut self, image: Handle<Image>) {
-        self.queue(SetImageRepeatingCommand { image })
+        self.set_image_address_mode(image, BackgroundAddressMode::default())
+    }
+
+    /// Queues this image to have it's [`SamplerDescriptor`] changed to match `address_mode` once
+    /// the image is loaded. This may take more than a frame to apply.
+    ///
+    /// Only worth doing when the image isn't shared with backgrounds using other address modes, the
+    /// shader applies [`BackgroundAddressMode`] either way but edges filter exactly when the sampler
+    /// matches.
+    fn set_image_address_mode(
+        &mut self,
+        image: Handle<Image>,
+        address_mode: BackgroundAddressMode,
+    ) {
+        self.queue(SetImageRepeatingCommand {
+            image,
+            address_mode,
+        })
+    }
+}
+
+/// Loads images with a repeating sampler, so they can be tiled from their first frame on without
+/// [`SetImageRepeatingExt`] patching them after loading.
+///
+/// The address modes are set through [`ImageLoaderSettings`], on top of the settings of the image's
+/// `.meta` file if it has one. Images without a sampler in their `.meta` file get
+/// [`ImageSamplerDescriptor::default`] with repeating address modes, which filters linearly even if
+/// the [`ImagePlugin`] defaults to nearest. Use [`load_repeating_with`](Self::load_repeating_with)
+/// to pick their sampler, e.g. [`ImageSamplerDescriptor::nearest`] for pixel art.
+pub trait LoadRepeatingExt {
+    fn load_repeating<'a>(&self, path: impl Into<AssetPath<'a>>) -> Handle<Image>;
+
+    fn load_repeating_with<'a>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        descriptor: ImageSamplerDescriptor,
+    ) -> Handle<Image>;
+
+    fn load_with_address_mode<'a>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        address_mode: BackgroundAddressMode,
+    ) -> Handle<Image>;
+}
+
+impl LoadRepeatingExt for AssetServer {
+    /// Loads an image with a sampler that repeats on both axes.
+    fn load_repeating<'a>(&self, path: impl Into<AssetPath<'a>>) -> Handle<Image> {
+        self.load_with_address_mode(path, BackgroundAddressMode::default())
+    }
+
+    /// Loads an image with a sampler that repeats on both axes, starting from `descriptor` if the
+    /// image's `.meta` file doesn't set a sampler.
+    fn load_repeating_with<'a>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        descriptor: ImageSamplerDescriptor,
+    ) -> Handle<Image> {
+        self.load_with_settings(path, move |settings: &mut ImageLoaderSettings| {
+            set_sampler_address_mode(
+                &mut settings.sampler,
+                BackgroundAddressMode::default(),
+                &descriptor,
+            );
+        })
+    }
+
+    /// Loads an image with a sampler matching `address_mode`.
+    fn load_with_address_mode<'a>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        address_mode: BackgroundAddressMode,
+    ) -> Handle<Image> {
+        self.load_with_settings(path, move |settings: &mut ImageLoaderSettings| {
+            set_sampler_address_mode(
+                &mut settings.sampler,
+                address_mode,
+                &ImageSamplerDescriptor::default(),
+            );
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::image::ImageFilterMode;
+
+    use super::*;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Shader>()
+            .init_asset::<Mesh>()
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .add_plugins(TilingBackgroundPlugin::<BackgroundMaterial>::default());
+        app
+    }
+
+    fn spawn_background(app: &mut App, texture: Handle<Image>) -> Entity {
+        let material = app
+            .world_mut()
+            .resource_mut::<Assets<BackgroundMaterial>>()
+            .add(BackgroundMaterial {
+                texture,
+                ..default()
+            });
+        app.world_mut()
+            .spawn((TilingBackground, MeshMaterial2d(material)))
+            .id()
+    }
+
+    fn load_state(app: &App, entity: Entity) -> BackgroundLoadState {
+        *app.world().get::<BackgroundLoadState>(entity).unwrap()
+    }
+
+    /// Updates the app until the background reaches `state`, the asset server loads on other
+    /// threads.
+    fn update_until(app: &mut App, entity: Entity, state: BackgroundLoadState) {
+        for _ in 0..500 {
+            app.update();
+            if load_state(app, entity) == state {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        panic!(
+            "background is {:?} instead of {state:?}",
+            load_state(app, entity)
+        );
+    }
+
+    /// Reads a packed background back the way `braymatter::bglib::background_instance` does.
+    fn unpack_background_instance(packed: &Affine3) -> (Vec2, Vec2, Vec2, LinearRgba) {
+        let [x_axis, y_axis, z_axis] = packed.matrix3.to_cols_array_2d();
+        let shift = packed.translation.y;
+        (
+            Vec2::new(x_axis[0], y_axis[1]) - shift,
+            Vec2::new(x_axis[1], x_axis[2]),
+            Vec2::new(y_axis[0], y_axis[2]),
+            LinearRgba::new(
+                z_axis[0],
+                z_axis[1],
+                z_axis[2] - shift,
+                packed.translation.x,
+            ),
+        )
+    }
+
+    #[test]
+    fn packed_background_instance_round_trips() {
+        let cases = [
+            (Vec2::ONE, Vec2::ZERO, Vec2::ZERO, LinearRgba::WHITE),
+            // Singular without a shift.
+            (Vec2::ZERO, Vec2::ZERO, Vec2::ZERO, LinearRgba::NONE),
+            (Vec2::ONE, Vec2::ZERO, Vec2::ZERO, LinearRgba::BLACK),
+            (
+                Vec2::new(-1.0, -2.0),
+                Vec2::ZERO,
+                Vec2::ZERO,
+                LinearRgba::new(0.0, 0.0, -3.0, 1.0),
+            ),
+            (
+                Vec2::new(0.5, 1.1),
+                Vec2::new(1.0e6, -250.0),
+                Vec2::new(123_456.75, -7.0e6),
+                LinearRgba::new(0.2, 0.4, 0.6, 0.8),
+            ),
+        ];
+        for (movement_scale, parallax_origin, offset, tint) in cases {
+            let mut packed = Affine3 {
+                matrix3: Mat3::IDENTITY,
+                translation: Vec3::new(0.0, 0.0, 4.5),
+            };
+            pack_background_instance(&mut packed, movement_scale, parallax_origin, offset, tint);
+
+            let (unpacked_movement, unpacked_origin, unpacked_offset, unpacked_tint) =
+                unpack_background_instance(&packed);
+            assert!(unpacked_movement.abs_diff_eq(movement_scale, 1.0e-6));
+            // Off the diagonal, so they are exact.
+            assert_eq!(unpacked_origin, parallax_origin);
+            assert_eq!(unpacked_offset, offset);
+            assert!(unpacked_tint.to_vec4().abs_diff_eq(tint.to_vec4(), 1.0e-6));
+            assert_eq!(packed.translation.z, 4.5);
+
+            let (inverse, last) = packed.inverse_transpose_3x3();
+            assert!(inverse.iter().all(|column| column.is_finite()) && last.is_finite());
+        }
+    }
+
+    #[test]
+    fn sampler_has_address_mode_per_axis() {
+        let repeat = BackgroundAddressMode::default();
+        assert!(!sampler_has_address_mode(&ImageSampler::Default, repeat));
+
+        let sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+            address_mode_u: ImageAddressMode::Repeat,
+            address_mode_v: ImageAddressMode::ClampToEdge,
+            ..default()
+        });
+        assert!(!sampler_has_address_mode(&sampler, repeat));
+        assert!(!sampler_has_address_mode(
+            &sampler,
+            BackgroundAddressMode::new(AxisAddressMode::MirrorRepeat)
+        ));
+        let mixed = BackgroundAddressMode {
+            y: AxisAddressMode::Clamp,
+            ..repeat
+        };
+        assert!(sampler_has_address_mode(&sampler, mixed));
+    }
+
+    #[test]
+    fn grid_frame_rect() {
+        let layouts = Assets::<TextureAtlasLayout>::default();
+        let mut flipbook = BackgroundFlipbook::from_grid(4, 2, 10.0);
+        assert_eq!(
+            flipbook.frame_rect(&layouts),
+            Some(Rect::new(0.0, 0.0, 0.25, 0.5))
+        );
+
+        flipbook.frame = 5;
+        assert_eq!(
+            flipbook.frame_rect(&layouts),
+            Some(Rect::new(0.25, 0.5, 0.5, 1.0))
+        );
+
+        flipbook = flipbook.with_frame_count(5);
+        assert_eq!(flipbook.frame_count(&layouts), Some(5));
+        assert_eq!(flipbook.frame_rect(&layouts), None);
+    }
+
+    #[test]
+    fn atlas_frame_rect() {
+        let mut layouts = Assets::<TextureAtlasLayout>::default();
+        let layout = layouts.add(TextureAtlasLayout::from_grid(
+            UVec2::new(16, 8),
+            2,
+            4,
+            None,
+            None,
+        ));
+        let mut flipbook = BackgroundFlipbook::from_atlas(layout, 10.0);
+        flipbook.frame = 3;
+        assert_eq!(flipbook.frame_count(&layouts), Some(8));
+        assert_eq!(
+            flipbook.frame_rect(&layouts),
+            Some(Rect::new(0.5, 0.25, 1.0, 0.5))
+        );
+
+        let unloaded = BackgroundFlipbook::from_atlas(Handle::default(), 10.0);
+        assert_eq!(unloaded.frame_count(&layouts), None);
+        assert_eq!(unloaded.frame_rect(&layouts), None);
+    }
+
+    #[test]
+    fn flipbook_without_fps_is_paused() {
+        for fps in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            assert!(BackgroundFlipbook::from_grid(2, 2, fps).timer.paused());
+        }
+        assert!(!BackgroundFlipbook::from_grid(2, 2, 10.0).timer.paused());
+    }
+
+    #[test]
+    fn set_sampler_address_mode_keeps_descriptor() {
+        let repeat = BackgroundAddressMode::default();
+        let mut sampler = ImageSampler::Default;
+        set_sampler_address_mode(&mut sampler, repeat, &ImageSamplerDescriptor::nearest());
+        assert!(sampler_has_address_mode(&sampler, repeat));
+        let ImageSampler::Descriptor(descriptor) = &sampler else {
+            panic!("sampler has no descriptor");
+        };
+        assert!(matches!(descriptor.mag_filter, ImageFilterMode::Nearest));
+
+        // Samplers that have a descriptor keep it, not the base one.
+        let mirror = BackgroundAddressMode::new(AxisAddressMode::MirrorRepeat);
+        set_sampler_address_mode(&mut sampler, mirror, &ImageSamplerDescriptor::linear());
+        assert!(sampler_has_address_mode(&sampler, mirror));
+        let ImageSampler::Descriptor(descriptor) = &sampler else {
+            panic!("sampler has no descriptor");
+        };
+        assert!(matches!(descriptor.mag_filter, ImageFilterMode::Nearest));
+    }
+
+    #[test]
+    fn runtime_image_gets_repeating_sampler() {
+        let mut app = app();
+        let image = app
+            .world_mut()
+            .resource_mut::<Assets<Image>>()
+            .add(Image::default());
+        let background = spawn_background(&mut app, image.clone());
+
+        update_until(&mut app, background, BackgroundLoadState::Ready);
+        let images = app.world().resource::<Assets<Image>>();
+        assert!(sampler_has_address_mode(
+            &images.get(&image).unwrap().sampler,
+            BackgroundAddressMode::default()
+        ));
+    }
+
+    #[test]
+    fn runtime_image_becomes_ready() {
+        let mut app = app();
+        let image = app
+            .world_mut()
+            .resource_mut::<Assets<Image>>()
+            .add(Image::default());
+        let background = spawn_background(&mut app, image);
+        assert_eq!(load_state(&app, background), BackgroundLoadState::Loading);
+
+        update_until(&mut app, background, BackgroundLoadState::Ready);
+        let ready = app.world().resource::<Events<BackgroundReady>>();
+        assert_eq!(
+            ready
+                .get_cursor()
+                .read(ready)
+                .map(|event| event.entity)
+                .collect::<Vec<_>>(),
+            [background]
+        );
+    }
+
+    #[test]
+    fn backgrounds_ready_needs_backgrounds() {
+        let mut app = app();
+        assert!(!app.world_mut().run_system_once(backgrounds_ready).unwrap());
+
+        let image = app
+            .world_mut()
+            .resource_mut::<Assets<Image>>()
+            .add(Image::default());
+        let background = spawn_background(&mut app, image);
+        update_until(&mut app, background, BackgroundLoadState::Ready);
+        assert!(app.world_mut().run_system_once(backgrounds_ready).unwrap());
+    }
+
+    #[test]
+    fn plugins_for_several_materials() {
+        let mut app = app();
+        app.add_plugins(TilingBackgroundPlugin::<LayeredBackgroundMaterial>::default());
+        let image = app
+            .world_mut()
+            .resource_mut::<Assets<Image>>()
+            .add(Image::default());
+        let background = spawn_background(&mut app, image);
+        update_until(&mut app, background, BackgroundLoadState::Ready);
+    }
+
+    #[test]
+    fn missing_image_fails_until_added() {
+        let mut app = app();
+        let image = app
+            .world()
+            .resource::<AssetServer>()
+            .load::<Image>("does_not_exist.png");
+        let background = spawn_background(&mut app, image.clone());
+
+        update_until(&mut app, background, BackgroundLoadState::Failed);
+        let failed = app.world().resource::<Events<BackgroundLoadFailed>>();
+        assert_eq!(failed.get_cursor().read(failed).count(), 1);
+
+        app.world_mut()
+            .resource_mut::<Assets<Image>>()
+            .insert(&image, Image::default());
+        update_until(&mut app, background, BackgroundLoadState::Ready);
+    }
+
+    #[test]
+    fn scaling_follows_material_swaps_and_removal() {
+        let mut app = app();
+        let background = spawn_background(&mut app, Handle::default());
+        app.world_mut()
+            .entity_mut(background)
+            .insert(BackgroundScaling::World);
+        app.update();
+
+        let scaling = |app: &App| {
+            let handle = app
+                .world()
+                .get::<MeshMaterial2d<BackgroundMaterial>>(background)
+                .unwrap();
+            app.world()
+                .resource::<Assets<BackgroundMaterial>>()
+                .get(&**handle)
+                .unwrap()
+                .scaling
+        };
+        assert_eq!(scaling(&app), BackgroundScaling::World.shader_value());
+
+        let swapped = app
+            .world_mut()
+            .resource_mut::<Assets<BackgroundMaterial>>()
+            .add(BackgroundMaterial::default());
+        app.world_mut()
+            .entity_mut(background)
+            .insert(MeshMaterial2d(swapped));
+        app.update();
+        assert_eq!(scaling(&app), BackgroundScaling::World.shader_value());
+
+        app.world_mut()
+            .entity_mut(background)
+            .remove::<BackgroundScaling>();
+        app.update();
+        assert_eq!(scaling(&app), BackgroundScaling::Screen.shader_value());
+    }
+
+    #[test]
+    fn invalid_pixel_grid_is_ignored() {
+        let mut app = app();
+        let background = spawn_background(&mut app, Handle::default());
+        app.world_mut()
+            .entity_mut(background)
+            .insert(BackgroundPixelSnap::Grid(0.0));
+        app.update();
+
+        let handle = app
+            .world()
+            .get::<MeshMaterial2d<BackgroundMaterial>>(background)
+            .unwrap();
+        let material = app
+            .world()
+            .resource::<Assets<BackgroundMaterial>>()
+            .get(&**handle)
+            .unwrap();
+        assert_eq!(material.pixel_snap, BackgroundPixelSnap::Off.shader_value());
+        assert!(material.pixel_grid > 0.0);
     }
 }
@@ -0,0 +1,366 @@
+//! [`BackgroundPass`], which draws backgrounds in a render pass of their own before the main 2d
+//! passes.
+
+use std::hash::Hash;
+use std::ops::Range;
+
+use bevy::core_pipeline::core_2d::graph::{Core2d, Node2d};
+use bevy::core_pipeline::tonemapping::{DebandDither, Tonemapping};
+use bevy::ecs::entity::EntityHashSet;
+use bevy::ecs::query::QueryItem;
+use bevy::math::FloatOrd;
+use bevy::prelude::*;
+use bevy::render::batching::no_gpu_preprocessing::batch_and_prepare_sorted_render_phase;
+use bevy::render::camera::ExtractedCamera;
+use bevy::render::mesh::RenderMesh;
+use bevy::render::render_asset::RenderAssets;
+use bevy::render::render_graph::{
+    NodeRunError, RenderGraphApp, RenderGraphContext, RenderLabel, ViewNode, ViewNodeRunner,
+};
+use bevy::render::render_phase::{
+    sort_phase_system, CachedRenderPipelinePhaseItem, DrawFunctionId, DrawFunctions, PhaseItem,
+    PhaseItemExtraIndex, SetItemPipeline, SortedPhaseItem, ViewSortedRenderPhases,
+};
+use bevy::render::render_resource::{
+    AsBindGroup, CachedRenderPipelineId, PipelineCache, RenderPassDescriptor,
+    SpecializedMeshPipelines, StoreOp,
+};
+use bevy::render::renderer::RenderContext;
+use bevy::render::sync_world::{MainEntity, RenderEntity};
+use bevy::render::view::{
+    ExtractedView, RenderVisibleEntities, ViewDepthTexture, ViewTarget, VisibilitySystems,
+    VisibleEntities,
+};
+use bevy::render::{Extract, ExtractSchedule, Render, RenderApp, RenderSet};
+use bevy::sprite::{
+    tonemapping_pipeline_key, DrawMesh2d, Material2d, Material2dKey, Material2dPipeline,
+    Mesh2dPipeline, Mesh2dPipelineKey, PreparedMaterial2d, RenderMaterial2dInstances,
+    RenderMesh2dInstances, SetMaterial2dBindGroup, SetMesh2dBindGroup, SetMesh2dViewBindGroup,
+};
+
+use crate::TilingBackground;
+
+/// Draws the backgrounds a 2d camera sees in a render pass of their own, before its opaque and
+/// transparent passes.
+///
+/// Without it backgrounds are sorted by their z translation along with sprites and other 2d meshes.
+/// With it they are always drawn behind them, sorted among each other by z, and the camera's main
+/// passes skip them. Add it to the camera entity.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Component, Default)]
+pub struct BackgroundPass;
+
+/// The render graph node drawing the [`BackgroundPass`], it runs between [`Node2d::StartMainPass`]
+/// and [`Node2d::MainOpaquePass`] of [`Core2d`].
+#[derive(RenderLabel, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BackgroundPassLabel;
+
+/// A background drawn in the [`BackgroundPass`].
+pub struct BackgroundPhaseItem {
+    pub sort_key: FloatOrd,
+    pub entity: (Entity, MainEntity),
+    pub pipeline: CachedRenderPipelineId,
+    pub draw_function: DrawFunctionId,
+    pub batch_range: Range<u32>,
+    pub extra_index: PhaseItemExtraIndex,
+}
+
+impl PhaseItem for BackgroundPhaseItem {
+    #[inline]
+    fn entity(&self) -> Entity {
+        self.entity.0
+    }
+
+    #[inline]
+    fn main_entity(&self) -> MainEntity {
+        self.entity.1
+    }
+
+    #[inline]
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+
+    #[inline]
+    fn batch_range(&self) -> &Range<u32> {
+        &self.batch_range
+    }
+
+    #[inline]
+    fn batch_range_mut(&mut self) -> &mut Range<u32> {
+        &mut self.batch_range
+    }
+
+    #[inline]
+    fn extra_index(&self) -> PhaseItemExtraIndex {
+        self.extra_index
+    }
+
+    #[inline]
+    fn batch_range_and_extra_index_mut(&mut self) -> (&mut Range<u32>, &mut PhaseItemExtraIndex) {
+        (&mut self.batch_range, &mut self.extra_index)
+    }
+}
+
+impl SortedPhaseItem for BackgroundPhaseItem {
+    type SortKey = FloatOrd;
+
+    #[inline]
+    fn sort_key(&self) -> Self::SortKey {
+        self.sort_key
+    }
+
+    #[inline]
+    fn sort(items: &mut [Self]) {
+        // Stable, so backgrounds at the same z don't flicker.
+        items.sort_by_key(Self::sort_key);
+    }
+}
+
+impl CachedRenderPipelinePhaseItem for BackgroundPhaseItem {
+    #[inline]
+    fn cached_pipeline(&self) -> CachedRenderPipelineId {
+        self.pipeline
+    }
+}
+
+/// The same as the draw function of [`Material2d`]s.
+pub(crate) type DrawBackground<M> = (
+    SetItemPipeline,
+    SetMesh2dViewBindGroup<0>,
+    SetMesh2dBindGroup<1>,
+    SetMaterial2dBindGroup<M, 2>,
+    DrawMesh2d,
+);
+
+/// Sets up the [`BackgroundPass`] for every material. Added once by the first
+/// [`TilingBackgroundPlugin`](crate::TilingBackgroundPlugin), which queues its own material with
+/// [`queue_background_pass`].
+pub(crate) struct BackgroundPassPlugin;
+
+impl Plugin for BackgroundPassPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<BackgroundPass>().add_systems(
+            PostUpdate,
+            move_backgrounds_to_pass.after(VisibilitySystems::CheckVisibility),
+        );
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .init_resource::<DrawFunctions<BackgroundPhaseItem>>()
+            .init_resource::<ViewSortedRenderPhases<BackgroundPhaseItem>>()
+            .add_systems(ExtractSchedule, extract_background_pass_phases)
+            .add_systems(
+                Render,
+                (
+                    sort_phase_system::<BackgroundPhaseItem>.in_set(RenderSet::PhaseSort),
+                    batch_and_prepare_sorted_render_phase::<BackgroundPhaseItem, Mesh2dPipeline>
+                        .in_set(RenderSet::PrepareResources),
+                ),
+            )
+            .add_render_graph_node::<ViewNodeRunner<BackgroundPassNode>>(
+                Core2d,
+                BackgroundPassLabel,
+            )
+            .add_render_graph_edges(
+                Core2d,
+                (
+                    Node2d::StartMainPass,
+                    BackgroundPassLabel,
+                    Node2d::MainOpaquePass,
+                ),
+            );
+    }
+}
+
+/// Moves the backgrounds a camera with a [`BackgroundPass`] sees out of the entities its main
+/// passes draw.
+fn move_backgrounds_to_pass(
+    mut cameras: Query<(&mut VisibleEntities, Has<BackgroundPass>)>,
+    backgrounds: Query<(), With<TilingBackground>>,
+) {
+    for (mut visible_entities, has_pass) in cameras.iter_mut() {
+        if !has_pass && visible_entities.is_empty::<With<TilingBackground>>() {
+            continue;
+        }
+        let mut pass_entities =
+            std::mem::take(visible_entities.get_mut::<With<TilingBackground>>());
+        pass_entities.clear();
+        if has_pass {
+            visible_entities.get_mut::<With<Mesh2d>>().retain(|entity| {
+                let is_background = backgrounds.contains(*entity);
+                if is_background {
+                    pass_entities.push(*entity);
+                }
+                !is_background
+            });
+        }
+        *visible_entities.get_mut::<With<TilingBackground>>() = pass_entities;
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn extract_background_pass_phases(
+    mut phases: ResMut<ViewSortedRenderPhases<BackgroundPhaseItem>>,
+    cameras: Extract<Query<(RenderEntity, &Camera), (With<Camera2d>, With<BackgroundPass>)>>,
+    mut live_entities: Local<EntityHashSet>,
+) {
+    live_entities.clear();
+
+    for (entity, camera) in &cameras {
+        if !camera.is_active {
+            continue;
+        }
+        phases.insert_or_clear(entity);
+        live_entities.insert(entity);
+    }
+
+    phases.retain(|camera_entity, _| live_entities.contains(camera_entity));
+}
+
+/// Queues the backgrounds using `M` for the [`BackgroundPass`] of each view, like
+/// [`queue_material2d_meshes`](bevy::sprite::queue_material2d_meshes) does for the main passes.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn queue_background_pass<M: Material2d>(
+    draw_functions: Res<DrawFunctions<BackgroundPhaseItem>>,
+    material2d_pipeline: Res<Material2dPipeline<M>>,
+    mut pipelines: ResMut<SpecializedMeshPipelines<Material2dPipeline<M>>>,
+    pipeline_cache: Res<PipelineCache>,
+    render_meshes: Res<RenderAssets<RenderMesh>>,
+    render_materials: Res<RenderAssets<PreparedMaterial2d<M>>>,
+    mut render_mesh_instances: ResMut<RenderMesh2dInstances>,
+    render_material_instances: Res<RenderMaterial2dInstances<M>>,
+    mut phases: ResMut<ViewSortedRenderPhases<BackgroundPhaseItem>>,
+    views: Query<(
+        Entity,
+        &ExtractedView,
+        &RenderVisibleEntities,
+        &Msaa,
+        Option<&Tonemapping>,
+        Option<&DebandDither>,
+    )>,
+) where
+    <M as AsBindGroup>::Data: PartialEq + Eq + Hash + Clone,
+{
+    if render_material_instances.is_empty() {
+        return;
+    }
+
+    let draw_background = draw_functions.read().id::<DrawBackground<M>>();
+    for (view_entity, view, visible_entities, msaa, tonemapping, dither) in &views {
+        let Some(phase) = phases.get_mut(&view_entity) else {
+            continue;
+        };
+
+        let mut view_key = Mesh2dPipelineKey::from_msaa_samples(msaa.samples())
+            | Mesh2dPipelineKey::from_hdr(view.hdr);
+        if !view.hdr {
+            if let Some(tonemapping) = tonemapping {
+                view_key |= Mesh2dPipelineKey::TONEMAP_IN_SHADER;
+                view_key |= tonemapping_pipeline_key(*tonemapping);
+            }
+            if let Some(DebandDither::Enabled) = dither {
+                view_key |= Mesh2dPipelineKey::DEBAND_DITHER;
+            }
+        }
+
+        for (render_entity, visible_entity) in visible_entities.iter::<With<TilingBackground>>() {
+            let Some(material_asset_id) = render_material_instances.get(visible_entity) else {
+                continue;
+            };
+            let Some(mesh_instance) = render_mesh_instances.get_mut(visible_entity) else {
+                continue;
+            };
+            let Some(material_2d) = render_materials.get(*material_asset_id) else {
+                continue;
+            };
+            let Some(mesh) = render_meshes.get(mesh_instance.mesh_asset_id) else {
+                continue;
+            };
+            let mesh_key = view_key
+                | Mesh2dPipelineKey::from_primitive_topology(mesh.primitive_topology())
+                | material_2d.properties.mesh_pipeline_key_bits;
+
+            let pipeline_id = match pipelines.specialize(
+                &pipeline_cache,
+                &material2d_pipeline,
+                Material2dKey {
+                    mesh_key,
+                    bind_group_data: material_2d.key.clone(),
+                },
+                &mesh.layout,
+            ) {
+                Ok(id) => id,
+                Err(err) => {
+                    error!("{}", err);
+                    continue;
+                }
+            };
+
+            mesh_instance.material_bind_group_id = material_2d.get_bind_group_id();
+            phase.add(BackgroundPhaseItem {
+                entity: (*render_entity, *visible_entity),
+                draw_function: draw_background,
+                pipeline: pipeline_id,
+                sort_key: FloatOrd(
+                    mesh_instance.transforms.world_from_local.translation.z
+                        + material_2d.properties.depth_bias,
+                ),
+                // Batching is done in `batch_and_prepare_sorted_render_phase`
+                batch_range: 0..1,
+                extra_index: PhaseItemExtraIndex::NONE,
+            });
+        }
+    }
+}
+
+/// Draws the [`BackgroundPhaseItem`]s of a view, see [`BackgroundPassLabel`].
+#[derive(Default)]
+struct BackgroundPassNode;
+
+impl ViewNode for BackgroundPassNode {
+    type ViewQuery = (
+        &'static ExtractedCamera,
+        &'static ViewTarget,
+        &'static ViewDepthTexture,
+    );
+
+    fn run<'w>(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        (camera, target, depth): QueryItem<'w, Self::ViewQuery>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.view_entity();
+        let Some(phase) = world
+            .resource::<ViewSortedRenderPhases<BackgroundPhaseItem>>()
+            .get(&view_entity)
+        else {
+            return Ok(());
+        };
+        if phase.items.is_empty() {
+            return Ok(());
+        }
+
+        // The first pass using the color and depth attachments clears them, so this clears the
+        // camera's background color as well.
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("background_pass_2d"),
+            color_attachments: &[Some(target.get_color_attachment())],
+            depth_stencil_attachment: Some(depth.get_attachment(StoreOp::Store)),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        if let Some(viewport) = camera.viewport.as_ref() {
+            render_pass.set_camera_viewport(viewport);
+        }
+        if let Err(err) = phase.render(&mut render_pass, world, view_entity) {
+            error!("Error encountered while rendering the background pass {err:?}");
+        }
+
+        Ok(())
+    }
+}
@@ -1,12 +1,35 @@
-#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
-#import bevy_sprite::mesh2d_view_bindings::view
-#import bevy_sprite::mesh2d_bindings
-#import bevy_sprite::mesh2d_functions::mesh2d_position_world_to_clip
-#import braymatter::bglib::scroll
+#import braymatter::bglib::{
+    BackgroundVertexOutput,
+    background_instance,
+    blend_output,
+    sample_frame,
+    snapped_layer_texture_uv,
+    strip_address_mode,
+    strip_fill,
+    strip_offset,
+    strip_uv,
+    tint_color,
+    unpremultiply,
+}
+// Keep the size a multiple of 16 bytes, webgl2 requires 16 byte alignment
 struct Uniforms {
-    scale: f32,
-    // webgl2 requires 16 byte alignment
-    _wasm_padding: vec3<f32>
+    tint: vec4<f32>,
+    border_color: vec4<f32>,
+    strip_fill_before: vec4<f32>,
+    strip_fill_after: vec4<f32>,
+    tile_scale: vec2<f32>,
+    uv_x_axis: vec2<f32>,
+    uv_y_axis: vec2<f32>,
+    uv_translation: vec2<f32>,
+    frame_min: vec2<f32>,
+    frame_size: vec2<f32>,
+    address_mode: vec2<u32>,
+    opacity: f32,
+    scaling: u32,
+    strip_axis: u32,
+    strip_position: f32,
+    pixel_snap: u32,
+    pixel_grid: f32,
 };
 
 @group(2) @binding(0)
@@ -16,13 +39,50 @@ var texture: texture_2d<f32>;
 @group(2) @binding(2)
 var texture_sampler: sampler;
 
+// Draws the background's mesh, a fullscreen triangle in clip space, passing on the instance so the
+// fragment shader can read the background's own parameters.
+@vertex
+fn vertex(
+    @location(0) position: vec3<f32>,
+    @builtin(instance_index) instance_index: u32,
+) -> BackgroundVertexOutput {
+    let uv = vec2<f32>(position.x + 1.0, 1.0 - position.y) * 0.5;
+    return BackgroundVertexOutput(vec4<f32>(position.xy, 0.0, 1.0), uv, instance_index);
+}
+
 @fragment
 fn fragment(
-    in: FullscreenVertexOutput,
+    in: BackgroundVertexOutput,
 ) -> @location(0) vec4<f32> {
-    let scale = uniforms.scale;
-    let offset = mesh2d_position_world_to_clip(vec4<f32>(view.world_position.xy, 0.0, 0.0)).xy;
-    let color = scroll(texture, texture_sampler, scale, in.uv, offset, view.viewport.zw);
-    return color;
+    let instance = background_instance(in.instance_index);
+    let tex_uv = strip_uv(snapped_layer_texture_uv(
+        in.uv,
+        instance.movement_scale,
+        instance.parallax_origin,
+        instance.offset + strip_offset(uniforms.strip_axis, uniforms.strip_position),
+        vec2<f32>(0.0),
+        uniforms.tile_scale,
+        uniforms.uv_x_axis,
+        uniforms.uv_y_axis,
+        uniforms.uv_translation,
+        uniforms.scaling,
+        vec2<f32>(textureDimensions(texture)) * uniforms.frame_size,
+        uniforms.pixel_snap,
+        uniforms.pixel_grid,
+    ), uniforms.strip_axis);
+    let address_mode = strip_address_mode(uniforms.address_mode, uniforms.strip_axis);
+    var color = sample_frame(
+        texture,
+        texture_sampler,
+        tex_uv,
+        address_mode,
+        uniforms.border_color,
+        uniforms.frame_min,
+        uniforms.frame_size,
+    );
+#ifdef BACKGROUND_PREMULTIPLIED_TEXTURE
+    color = unpremultiply(color);
+#endif
+    color = strip_fill(color, tex_uv, uniforms.strip_axis, uniforms.strip_fill_before, uniforms.strip_fill_after);
+    return blend_output(tint_color(color, uniforms.tint * instance.tint, uniforms.opacity));
 }
-
@@ -1,9 +1,64 @@
 #define_import_path braymatter::bglib
 
+#import bevy_sprite::mesh2d_functions::get_world_from_local
+#import bevy_sprite::mesh2d_view_bindings::view
+
+// Values of `BackgroundScaling::shader_value`
+const SCALING_SCREEN: u32 = 0u;
+const SCALING_WORLD: u32 = 1u;
+
+// Values of `AxisAddressMode::shader_value`
+const ADDRESS_REPEAT: u32 = 0u;
+const ADDRESS_MIRROR_REPEAT: u32 = 1u;
+const ADDRESS_CLAMP: u32 = 2u;
+const ADDRESS_CLAMP_TO_BORDER: u32 = 3u;
+
+// Values of `StripAxis::shader_value`, 0 means the layer isn't a strip
+const STRIP_NONE: u32 = 0u;
+const STRIP_HORIZONTAL: u32 = 1u;
+const STRIP_VERTICAL: u32 = 2u;
+
+// Values of `BackgroundPixelSnap::shader_value`
+const SNAP_OFF: u32 = 0u;
+const SNAP_TEXEL: u32 = 1u;
+const SNAP_GRID: u32 = 2u;
+
+// Output of the `vertex` entry point of the background shader, which materials can use as their
+// vertex shader to draw fullscreen.
+struct BackgroundVertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) @interpolate(flat) instance_index: u32,
+};
+
+// The parameters of a single background, which backgrounds sharing a material can each set.
+struct BackgroundInstance {
+    movement_scale: vec2<f32>,
+    parallax_origin: vec2<f32>,
+    // `BackgroundOffset::offset` plus the scroll position.
+    offset: vec2<f32>,
+    tint: vec4<f32>,
+}
+
+// Reads the parameters of the background drawn by `instance_index`. The plugin packs them into the
+// background's mesh transform, which isn't needed to draw it fullscreen, see
+// `pack_background_instance`. It adds the number in `packed[3].y` to the diagonal, so Bevy can
+// invert the transform.
+fn background_instance(instance_index: u32) -> BackgroundInstance {
+    let packed = get_world_from_local(instance_index);
+    let shift = packed[3].y;
+    return BackgroundInstance(
+        vec2<f32>(packed[0].x, packed[1].y) - shift,
+        packed[0].yz,
+        packed[1].xz,
+        vec4<f32>(packed[2].xy, packed[2].z - shift, packed[3].x),
+    );
+}
+
 fn scroll(
     tex: texture_2d<f32>,
     texture_sampler: sampler,
-    scale: f32,
+    scale: vec2<f32>,
     uv: vec2<f32>,
     offset: vec2<f32>,
     viewport_size: vec2<f32>,
@@ -18,4 +73,316 @@ fn scroll(
     let color = textureSample(tex, texture_sampler, new_uv);
 
     return color;
-}
\ No newline at end of file
+}
+
+// The world position shown at a fullscreen uv, taking the camera's projection into account.
+fn world_position(uv: vec2<f32>) -> vec2<f32> {
+    let clip = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    let world = view.world_from_clip * clip;
+    return world.xy / world.w;
+}
+
+// Moves a world position into the layer's space, a scale of 1.0 keeps the layer fixed in the world
+// and 0.0 keeps it fixed to the camera. There is no parallax while the camera is at `origin`.
+fn parallax(world: vec2<f32>, scale: vec2<f32>, origin: vec2<f32>) -> vec2<f32> {
+    return world - (view.world_position.xy - origin) * (1.0 - scale);
+}
+
+// Applies an affine transform given as the columns of its matrix and a translation, e.g. to rotate or
+// skew the tiling grid of a layer.
+fn affine_transform(
+    position: vec2<f32>,
+    x_axis: vec2<f32>,
+    y_axis: vec2<f32>,
+    translation: vec2<f32>,
+) -> vec2<f32> {
+    return mat2x2<f32>(x_axis, y_axis) * position + translation;
+}
+
+// How many texels fit in one world unit for the given scaling mode.
+fn texels_per_world_unit(scaling: u32) -> vec2<f32> {
+    if scaling == SCALING_WORLD {
+        return vec2<f32>(1.0);
+    }
+    // Size of the view in world units, so one texel ends up covering one physical pixel.
+    let view_size = vec2<f32>(length(view.world_from_clip[0].xy), length(view.world_from_clip[1].xy)) * 2.0;
+    return view.viewport.zw / view_size;
+}
+
+// Converts a position in the layer's space to texture uvs, flipping y since textures grow downwards.
+// `tile_scale` grows (or shrinks) each tile.
+fn layer_uv(position: vec2<f32>, tile_scale: vec2<f32>, scaling: u32, tex_dim: vec2<f32>) -> vec2<f32> {
+    let texel = position * texels_per_world_unit(scaling) / tile_scale;
+    return vec2<f32>(texel.x, -texel.y) / tex_dim;
+}
+
+// Computes the texture uvs of a tiling layer at a fullscreen uv, see `BackgroundMaterial` for the
+// meaning of the arguments. The result isn't wrapped, see `sample_addressed`.
+fn layer_texture_uv(
+    uv: vec2<f32>,
+    scale: vec2<f32>,
+    parallax_origin: vec2<f32>,
+    offset: vec2<f32>,
+    scroll: vec2<f32>,
+    tile_scale: vec2<f32>,
+    uv_x_axis: vec2<f32>,
+    uv_y_axis: vec2<f32>,
+    uv_translation: vec2<f32>,
+    scaling: u32,
+    tex_dim: vec2<f32>,
+) -> vec2<f32> {
+    return snapped_layer_texture_uv(
+        uv,
+        scale,
+        parallax_origin,
+        offset,
+        scroll,
+        tile_scale,
+        uv_x_axis,
+        uv_y_axis,
+        uv_translation,
+        scaling,
+        tex_dim,
+        SNAP_OFF,
+        1.0,
+    );
+}
+
+// `layer_texture_uv` with one of the `SNAP_*` modes, `pixel_grid` is only used by `SNAP_GRID`.
+fn snapped_layer_texture_uv(
+    uv: vec2<f32>,
+    scale: vec2<f32>,
+    parallax_origin: vec2<f32>,
+    offset: vec2<f32>,
+    scroll: vec2<f32>,
+    tile_scale: vec2<f32>,
+    uv_x_axis: vec2<f32>,
+    uv_y_axis: vec2<f32>,
+    uv_translation: vec2<f32>,
+    scaling: u32,
+    tex_dim: vec2<f32>,
+    pixel_snap: u32,
+    pixel_grid: f32,
+) -> vec2<f32> {
+    let world = world_position(uv);
+    // How far the layer has moved away from the world.
+    let shift = world - parallax(world, scale, parallax_origin) + offset + scroll;
+    let layer_position = world - snap_shift(shift, pixel_snap, pixel_grid, tile_scale, scaling);
+    let position = affine_transform(layer_position, uv_x_axis, uv_y_axis, uv_translation);
+    return snap_uv(layer_uv(position, tile_scale, scaling, tex_dim), pixel_snap, tex_dim);
+}
+
+// Rounds the distance a layer has moved to whole texels or pixels of the grid, so the texels of
+// slower layers don't shift around between screen pixels.
+fn snap_shift(
+    shift: vec2<f32>,
+    pixel_snap: u32,
+    pixel_grid: f32,
+    tile_scale: vec2<f32>,
+    scaling: u32,
+) -> vec2<f32> {
+    switch pixel_snap {
+        case SNAP_TEXEL: {
+            let texel_size = tile_scale / texels_per_world_unit(scaling);
+            return round(shift / texel_size) * texel_size;
+        }
+        case SNAP_GRID: {
+            return round(shift / pixel_grid) * pixel_grid;
+        }
+        default: {
+            return shift;
+        }
+    }
+}
+
+// Moves snapped uvs to the center of their texel, which is what a nearest sampler would pick.
+fn snap_uv(tex_uv: vec2<f32>, pixel_snap: u32, tex_dim: vec2<f32>) -> vec2<f32> {
+    if pixel_snap == SNAP_OFF {
+        return tex_uv;
+    }
+    return (floor(tex_uv * tex_dim) + 0.5) / tex_dim;
+}
+
+// Samples a tiling layer at a fullscreen uv, relying on the sampler to repeat the texture.
+fn scroll_layer(
+    tex: texture_2d<f32>,
+    texture_sampler: sampler,
+    uv: vec2<f32>,
+    scale: vec2<f32>,
+    parallax_origin: vec2<f32>,
+    offset: vec2<f32>,
+    scroll: vec2<f32>,
+    tile_scale: vec2<f32>,
+    uv_x_axis: vec2<f32>,
+    uv_y_axis: vec2<f32>,
+    uv_translation: vec2<f32>,
+    scaling: u32,
+) -> vec4<f32> {
+    let tex_uv = layer_texture_uv(
+        uv,
+        scale,
+        parallax_origin,
+        offset,
+        scroll,
+        tile_scale,
+        uv_x_axis,
+        uv_y_axis,
+        uv_translation,
+        scaling,
+        vec2<f32>(textureDimensions(tex)),
+    );
+    return textureSample(tex, texture_sampler, tex_uv);
+}
+
+// Wraps a single texture coordinate with one of the `ADDRESS_*` modes. Clamped coordinates stay half
+// a texel away from the edge so filtering doesn't pick up the other side of a repeating sampler.
+fn address_coordinate(coordinate: f32, mode: u32, half_texel: f32) -> f32 {
+    switch mode {
+        case ADDRESS_MIRROR_REPEAT: {
+            let mirrored = 1.0 - abs(1.0 - fract(coordinate * 0.5) * 2.0);
+            return clamp(mirrored, half_texel, 1.0 - half_texel);
+        }
+        case ADDRESS_CLAMP, ADDRESS_CLAMP_TO_BORDER: {
+            return clamp(coordinate, half_texel, 1.0 - half_texel);
+        }
+        default: {
+            return fract(coordinate);
+        }
+    }
+}
+
+// Samples a texture at unwrapped uvs, wrapping each axis with one of the `ADDRESS_*` modes. Axes using
+// `ADDRESS_CLAMP_TO_BORDER` show `border_color` outside of the texture.
+fn sample_addressed(
+    tex: texture_2d<f32>,
+    texture_sampler: sampler,
+    tex_uv: vec2<f32>,
+    address_mode: vec2<u32>,
+    border_color: vec4<f32>,
+) -> vec4<f32> {
+    return sample_frame(tex, texture_sampler, tex_uv, address_mode, border_color, vec2<f32>(0.0), vec2<f32>(1.0));
+}
+
+// `sample_addressed` for a frame of a sprite sheet, `tex_uv` are uvs of the frame and are wrapped
+// within it. `frame_min` and `frame_size` are the frame's rect in the texture's uvs.
+fn sample_frame(
+    tex: texture_2d<f32>,
+    texture_sampler: sampler,
+    tex_uv: vec2<f32>,
+    address_mode: vec2<u32>,
+    border_color: vec4<f32>,
+    frame_min: vec2<f32>,
+    frame_size: vec2<f32>,
+) -> vec4<f32> {
+    // Wrapped uvs stay half a texel inside of the frame, so neighboring frames don't bleed in.
+    let half_texel = 0.5 / (vec2<f32>(textureDimensions(tex)) * frame_size);
+    let wrapped = vec2<f32>(
+        address_coordinate(tex_uv.x, address_mode.x, half_texel.x),
+        address_coordinate(tex_uv.y, address_mode.y, half_texel.y),
+    );
+    // The sampler can't repeat a frame, so repeating axes are kept inside of it too.
+    let inset = select(vec2<f32>(0.0), half_texel, frame_size < vec2<f32>(1.0));
+    let frame_uv = frame_min + clamp(wrapped, inset, 1.0 - inset) * frame_size;
+    // Gradients of the unwrapped uvs, so wrapping doesn't cause seams where the uvs jump.
+    let color = textureSampleGrad(
+        tex,
+        texture_sampler,
+        frame_uv,
+        dpdx(tex_uv) * frame_size,
+        dpdy(tex_uv) * frame_size,
+    );
+
+    let outside = tex_uv < vec2<f32>(0.0) || tex_uv > vec2<f32>(1.0);
+    let border = address_mode == vec2<u32>(ADDRESS_CLAMP_TO_BORDER);
+    return select(color, border_color, any(outside && border));
+}
+
+// Multiplies a straight (not premultiplied) alpha color by a tint and fades it by `opacity`.
+// Use `premultiply` on the result when blending with premultiplied alpha.
+fn tint_color(color: vec4<f32>, tint: vec4<f32>, opacity: f32) -> vec4<f32> {
+    let tinted = color * tint;
+    return vec4<f32>(tinted.rgb, tinted.a * opacity);
+}
+
+fn premultiply(color: vec4<f32>) -> vec4<f32> {
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
+
+// Converts a premultiplied color back to straight alpha.
+fn unpremultiply(color: vec4<f32>) -> vec4<f32> {
+    return select(vec4<f32>(0.0), vec4<f32>(color.rgb / color.a, color.a), color.a > 0.0);
+}
+
+// Converts a straight alpha color to what the blend state of the background's `BackgroundBlendMode`
+// expects, using the shader defs set by `BackgroundBlendMode::specialize_pipeline`.
+fn blend_output(color: vec4<f32>) -> vec4<f32> {
+#ifdef BACKGROUND_OPAQUE
+    return vec4<f32>(color.rgb, 1.0);
+#else ifdef BACKGROUND_PREMULTIPLIED_OUTPUT
+    return premultiply(color);
+#else
+    return color;
+#endif
+}
+
+// The offset that moves a strip's bottom (or left) edge to `position` in the layer's space, add it to
+// the layer's offset. Horizontal strips also need `strip_uv` since textures grow downwards.
+fn strip_offset(axis: u32, position: f32) -> vec2<f32> {
+    switch axis {
+        case STRIP_HORIZONTAL: {
+            return vec2<f32>(0.0, position);
+        }
+        case STRIP_VERTICAL: {
+            return vec2<f32>(position, 0.0);
+        }
+        default: {
+            return vec2<f32>(0.0);
+        }
+    }
+}
+
+// Shifts the texture uvs of a horizontal strip so the texture sits above its anchor instead of below.
+fn strip_uv(tex_uv: vec2<f32>, axis: u32) -> vec2<f32> {
+    if axis == STRIP_HORIZONTAL {
+        return tex_uv + vec2<f32>(0.0, 1.0);
+    }
+    return tex_uv;
+}
+
+// Clamps the strip's axis, so the texture is only shown once across it.
+fn strip_address_mode(address_mode: vec2<u32>, axis: u32) -> vec2<u32> {
+    switch axis {
+        case STRIP_HORIZONTAL: {
+            return vec2<u32>(address_mode.x, ADDRESS_CLAMP);
+        }
+        case STRIP_VERTICAL: {
+            return vec2<u32>(ADDRESS_CLAMP, address_mode.y);
+        }
+        default: {
+            return address_mode;
+        }
+    }
+}
+
+// Replaces colors outside of a strip with `before`, below or left of it, and `after`, above or right
+// of it.
+fn strip_fill(
+    color: vec4<f32>,
+    tex_uv: vec2<f32>,
+    axis: u32,
+    before: vec4<f32>,
+    after: vec4<f32>,
+) -> vec4<f32> {
+    switch axis {
+        case STRIP_HORIZONTAL: {
+            // The top of the texture is at 0, so above the strip is negative.
+            return select(select(color, before, tex_uv.y > 1.0), after, tex_uv.y < 0.0);
+        }
+        case STRIP_VERTICAL: {
+            return select(select(color, after, tex_uv.x > 1.0), before, tex_uv.x < 0.0);
+        }
+        default: {
+            return color;
+        }
+    }
+}
@@ -0,0 +1,104 @@
+#import braymatter::bglib::{
+    BackgroundInstance,
+    BackgroundVertexOutput,
+    background_instance,
+    blend_output,
+    premultiply,
+    scroll_layer,
+    tint_color,
+    unpremultiply,
+}
+
+// `MAX_BACKGROUND_LAYERS`
+const MAX_LAYERS: u32 = 4u;
+
+struct Layer {
+    tint: vec4<f32>,
+    movement_scale: vec2<f32>,
+    parallax_origin: vec2<f32>,
+    offset: vec2<f32>,
+    tile_scale: vec2<f32>,
+};
+
+struct Uniforms {
+    layers: array<Layer, MAX_LAYERS>,
+    layer_count: u32,
+    scaling: u32,
+    opacity: f32,
+};
+
+@group(2) @binding(0)
+var<uniform> uniforms: Uniforms;
+@group(2) @binding(1)
+var texture_0: texture_2d<f32>;
+@group(2) @binding(2)
+var sampler_0: sampler;
+@group(2) @binding(3)
+var texture_1: texture_2d<f32>;
+@group(2) @binding(4)
+var sampler_1: sampler;
+@group(2) @binding(5)
+var texture_2: texture_2d<f32>;
+@group(2) @binding(6)
+var sampler_2: sampler;
+@group(2) @binding(7)
+var texture_3: texture_2d<f32>;
+@group(2) @binding(8)
+var sampler_3: sampler;
+
+// Samples a single layer, scrolled by the background's own offset and scroll position as well.
+fn sample_layer(
+    index: u32,
+    tex: texture_2d<f32>,
+    texture_sampler: sampler,
+    uv: vec2<f32>,
+    instance: BackgroundInstance,
+) -> vec4<f32> {
+    let layer = uniforms.layers[index];
+    var color = scroll_layer(
+        tex,
+        texture_sampler,
+        uv,
+        layer.movement_scale,
+        layer.parallax_origin,
+        layer.offset + instance.offset,
+        vec2<f32>(0.0),
+        layer.tile_scale,
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0),
+        uniforms.scaling,
+    );
+#ifdef BACKGROUND_PREMULTIPLIED_TEXTURE
+    color = unpremultiply(color);
+#endif
+    return color * layer.tint;
+}
+
+// Blends a straight alpha color over a premultiplied one.
+fn over(below: vec4<f32>, color: vec4<f32>) -> vec4<f32> {
+    return premultiply(color) + below * (1.0 - color.a);
+}
+
+@fragment
+fn fragment(
+    in: BackgroundVertexOutput,
+) -> @location(0) vec4<f32> {
+    let instance = background_instance(in.instance_index);
+    // Layers are composited back to front with premultiplied alpha.
+    var color = vec4<f32>(0.0);
+    if uniforms.layer_count > 0u {
+        color = over(color, sample_layer(0u, texture_0, sampler_0, in.uv, instance));
+    }
+    if uniforms.layer_count > 1u {
+        color = over(color, sample_layer(1u, texture_1, sampler_1, in.uv, instance));
+    }
+    if uniforms.layer_count > 2u {
+        color = over(color, sample_layer(2u, texture_2, sampler_2, in.uv, instance));
+    }
+    if uniforms.layer_count > 3u {
+        color = over(color, sample_layer(3u, texture_3, sampler_3, in.uv, instance));
+    }
+
+    return blend_output(tint_color(unpremultiply(color), instance.tint, uniforms.opacity));
+}